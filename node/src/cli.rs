@@ -1,4 +1,7 @@
+use crate::chain_spec;
+use node_template_runtime::AccountId;
 use sc_cli::RunCmd;
+use sp_core::crypto::Ss58Codec;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -13,7 +16,7 @@ pub struct Cli {
 #[derive(Debug, StructOpt)]
 pub enum Subcommand {
 	/// Key management cli utilities
-	Key(sc_cli::KeySubcommand),
+	Key(KeySubcommand),
 	/// Build a chain specification.
 	BuildSpec(sc_cli::BuildSpecCmd),
 
@@ -39,3 +42,103 @@ pub enum Subcommand {
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 }
+
+#[derive(Debug, StructOpt)]
+pub enum KeySubcommand {
+	#[structopt(flatten)]
+	Substrate(sc_cli::KeySubcommand),
+
+	/// Compute the address of a multisig account offline.
+	DeriveMultisig(DeriveMultisigCmd),
+}
+
+/// The `derive-multisig` command, printing the account ID of the multisig of `signatories`
+/// approving with `threshold` of them, as `pallet_multisig` derives it.
+#[derive(Debug, StructOpt)]
+pub struct DeriveMultisigCmd {
+	/// The number of signatories which have to approve a call.
+	#[structopt(long)]
+	pub threshold: u16,
+
+	/// The SS58 addresses of the signatories, in any order.
+	#[structopt(required = true, min_values = 2)]
+	pub signatories: Vec<String>,
+}
+
+impl DeriveMultisigCmd {
+	pub fn run(&self) -> sc_cli::Result<()> {
+		println!("{}", self.multisig()?.to_ss58check());
+		Ok(())
+	}
+
+	/// The account ID of the multisig, after checking the signatories and the threshold.
+	pub fn multisig(&self) -> sc_cli::Result<AccountId> {
+		let mut signatories = self
+			.signatories
+			.iter()
+			.map(|address| {
+				AccountId::from_ss58check(address).map_err(|e| {
+					sc_cli::Error::Input(format!("Invalid address {}: {:?}", address, e))
+				})
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		signatories.sort();
+		signatories.dedup();
+		if signatories.len() != self.signatories.len() {
+			return Err(sc_cli::Error::Input("Duplicate signatories".into()))
+		}
+		if self.threshold < 1 || self.threshold as usize > signatories.len() {
+			return Err(sc_cli::Error::Input(format!(
+				"The threshold has to be between 1 and {}",
+				signatories.len()
+			)))
+		}
+
+		Ok(chain_spec::multisig_account_id(signatories, self.threshold))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chain_spec::get_account_id_from_seed;
+	use node_template_runtime::Runtime;
+	use sp_core::sr25519;
+
+	fn derive_multisig(args: &[&str]) -> DeriveMultisigCmd {
+		let args = ["node-template", "key", "derive-multisig"].iter().chain(args);
+		match Cli::from_iter(args).subcommand {
+			Some(Subcommand::Key(KeySubcommand::DeriveMultisig(cmd))) => cmd,
+			subcommand => panic!("unexpected subcommand {:?}", subcommand),
+		}
+	}
+
+	#[test]
+	fn derive_multisig_should_match_pallet_multisig() {
+		let [alice, bob, charlie] =
+			["Alice", "Bob", "Charlie"].map(get_account_id_from_seed::<sr25519::Public>);
+		let addresses = [charlie.clone(), alice.clone(), bob.clone()].map(|a| a.to_ss58check());
+		let cmd =
+			derive_multisig(&["--threshold", "2", &addresses[0], &addresses[1], &addresses[2]]);
+
+		// `pallet_multisig` expects the signatories sorted.
+		let mut signatories = vec![alice, bob, charlie];
+		signatories.sort();
+		assert_eq!(
+			cmd.multisig().unwrap(),
+			pallet_multisig::Pallet::<Runtime>::multi_account_id(&signatories, 2)
+		);
+	}
+
+	#[test]
+	fn derive_multisig_should_reject_bad_signatories_and_thresholds() {
+		let [alice, bob] = ["Alice", "Bob"]
+			.map(|seed| get_account_id_from_seed::<sr25519::Public>(seed).to_ss58check());
+		assert!(derive_multisig(&["--threshold", "2", &alice, &alice]).multisig().is_err());
+		assert!(derive_multisig(&["--threshold", "3", &alice, &bob]).multisig().is_err());
+		assert!(derive_multisig(&["--threshold", "0", &alice, &bob]).multisig().is_err());
+		assert!(derive_multisig(&["--threshold", "1", &alice, "not an address"])
+			.multisig()
+			.is_err());
+	}
+}
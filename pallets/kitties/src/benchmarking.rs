@@ -0,0 +1,124 @@
+//! Benchmarking setup for pallet-kitties
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Kitties;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	sp_runtime::{traits::Bounded, DispatchError},
+	traits::{tokens::fungibles, Currency},
+};
+use frame_system::RawOrigin;
+use sp_std::{prelude::*, vec};
+
+type BalanceOf<T> = <<T as Config>::Currency as fungibles::Inspect<
+	<T as frame_system::Config>::AccountId,
+>>::Balance;
+type DepositOf<T> = <<T as Config>::ReservableCurrency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::Balance;
+
+/// Give `who` enough to reserve as many kitties as it may own, and to buy one.
+fn fund<T: Config>(who: &T::AccountId) {
+	T::ReservableCurrency::make_free_balance_be(who, DepositOf::<T>::max_value() / 2u32.into());
+}
+
+/// Mint `n` kitties owned by `owner`, each with its own dna starting with `salt`.
+fn kitties<T: Config>(
+	owner: &T::AccountId,
+	salt: u8,
+	n: u32,
+) -> Result<Vec<T::Hash>, DispatchError> {
+	(0..n)
+		.map(|i| {
+			let mut dna = [salt; 16];
+			dna[1..5].copy_from_slice(&i.to_le_bytes());
+			Kitties::<T>::mint(owner, Some(dna), Some(Gender::Male)).map_err(Into::into)
+		})
+		.collect()
+}
+
+/// The price of kitties up for sale, in the default currency.
+fn price<T: Config>() -> (T::AssetId, BalanceOf<T>)
+where
+	T::AssetId: Default,
+{
+	let asset = T::AssetId::default();
+	let amount = <T::Currency as fungibles::Inspect<_>>::minimum_balance(asset) * 1000u32.into();
+	(asset, amount)
+}
+
+benchmarks! {
+	where_clause { where T::AssetId: Default }
+
+	// `n` is the number of kitties the caller already owns.
+	create_kitty {
+		let n in 0 .. T::MaxKittyOwned::get() - 1;
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		kitties::<T>(&caller, 0, n)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(KittiesOwned::<T>::get(&caller).len() as u32, n + 1);
+	}
+
+	set_price {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let kitty_id = kitties::<T>(&caller, 0, 1)?[0];
+		let new_price = Some(price::<T>());
+	}: _(RawOrigin::Signed(caller), kitty_id, new_price)
+	verify {
+		assert_eq!(Kitties::<T>::kitties(&kitty_id).unwrap().price, new_price);
+	}
+
+	// `n` is the number of kitties both the sender and the receiver already own.
+	transfer {
+		let n in 0 .. T::MaxKittyOwned::get() - 1;
+		let caller: T::AccountId = whitelisted_caller();
+		let to: T::AccountId = account("to", 0, 0);
+		fund::<T>(&caller);
+		fund::<T>(&to);
+		kitties::<T>(&caller, 0, n)?;
+		kitties::<T>(&to, 1, n)?;
+		let kitty_id = kitties::<T>(&caller, 2, 1)?[0];
+	}: _(RawOrigin::Signed(caller), to.clone(), kitty_id)
+	verify {
+		assert_eq!(Kitties::<T>::kitties(&kitty_id).unwrap().owner, to);
+	}
+
+	// `n` is the number of kitties both the seller and the buyer already own.
+	buy_kitty {
+		let n in 0 .. T::MaxKittyOwned::get() - 1;
+		let caller: T::AccountId = whitelisted_caller();
+		let seller: T::AccountId = account("seller", 0, 0);
+		fund::<T>(&caller);
+		fund::<T>(&seller);
+		kitties::<T>(&caller, 0, n)?;
+		kitties::<T>(&seller, 1, n)?;
+		let kitty_id = kitties::<T>(&seller, 2, 1)?[0];
+		let (asset, amount) = price::<T>();
+		Kitties::<T>::set_price(
+			RawOrigin::Signed(seller).into(),
+			kitty_id,
+			Some((asset, amount)),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, asset, amount)
+	verify {
+		assert_eq!(Kitties::<T>::kitties(&kitty_id).unwrap().owner, caller);
+	}
+
+	// `n` is the number of kitties the caller already owns, the parents included.
+	breed_kitty {
+		let n in 2 .. T::MaxKittyOwned::get() - 1;
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let parents = kitties::<T>(&caller, 0, n)?;
+	}: _(RawOrigin::Signed(caller.clone()), parents[0], parents[1])
+	verify {
+		assert_eq!(KittiesOwned::<T>::get(&caller).len() as u32, n + 1);
+	}
+
+	impl_benchmark_test_suite!(Kitties, crate::mock::new_test_ext(), crate::mock::Test);
+}
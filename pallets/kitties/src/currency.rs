@@ -0,0 +1,118 @@
+//! Prices kitties in the native currency or in any asset, through one `fungibles` implementation.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	sp_runtime::{DispatchError, RuntimeDebug},
+	traits::tokens::{fungible, fungibles, DepositConsequence, WithdrawConsequence},
+};
+use scale_info::TypeInfo;
+use sp_std::marker::PhantomData;
+
+/// A currency kitties can be priced in.
+#[derive(
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub enum NativeOrAsset<AssetId> {
+	/// The native currency.
+	Native,
+	/// The asset with the given id.
+	Asset(AssetId),
+}
+
+impl<AssetId> Default for NativeOrAsset<AssetId> {
+	fn default() -> Self {
+		Self::Native
+	}
+}
+
+/// Implements `fungibles` over the native currency `Native`, as `NativeOrAsset::Native`, and the
+/// assets of `Assets`.
+pub struct NativeOrAssets<Native, Assets>(PhantomData<(Native, Assets)>);
+
+impl<AccountId, Native, Assets> fungibles::Inspect<AccountId> for NativeOrAssets<Native, Assets>
+where
+	Native: fungible::Inspect<AccountId>,
+	Assets: fungibles::Inspect<AccountId, Balance = Native::Balance>,
+{
+	type AssetId = NativeOrAsset<Assets::AssetId>;
+	type Balance = Native::Balance;
+
+	fn total_issuance(asset: Self::AssetId) -> Self::Balance {
+		match asset {
+			NativeOrAsset::Native => Native::total_issuance(),
+			NativeOrAsset::Asset(id) => Assets::total_issuance(id),
+		}
+	}
+
+	fn minimum_balance(asset: Self::AssetId) -> Self::Balance {
+		match asset {
+			NativeOrAsset::Native => Native::minimum_balance(),
+			NativeOrAsset::Asset(id) => Assets::minimum_balance(id),
+		}
+	}
+
+	fn balance(asset: Self::AssetId, who: &AccountId) -> Self::Balance {
+		match asset {
+			NativeOrAsset::Native => Native::balance(who),
+			NativeOrAsset::Asset(id) => Assets::balance(id, who),
+		}
+	}
+
+	fn reducible_balance(asset: Self::AssetId, who: &AccountId, keep_alive: bool) -> Self::Balance {
+		match asset {
+			NativeOrAsset::Native => Native::reducible_balance(who, keep_alive),
+			NativeOrAsset::Asset(id) => Assets::reducible_balance(id, who, keep_alive),
+		}
+	}
+
+	fn can_deposit(
+		asset: Self::AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> DepositConsequence {
+		match asset {
+			NativeOrAsset::Native => Native::can_deposit(who, amount),
+			NativeOrAsset::Asset(id) => Assets::can_deposit(id, who, amount),
+		}
+	}
+
+	fn can_withdraw(
+		asset: Self::AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> WithdrawConsequence<Self::Balance> {
+		match asset {
+			NativeOrAsset::Native => Native::can_withdraw(who, amount),
+			NativeOrAsset::Asset(id) => Assets::can_withdraw(id, who, amount),
+		}
+	}
+}
+
+impl<AccountId, Native, Assets> fungibles::Transfer<AccountId> for NativeOrAssets<Native, Assets>
+where
+	Native: fungible::Transfer<AccountId>,
+	Assets: fungibles::Transfer<AccountId, Balance = Native::Balance>,
+{
+	fn transfer(
+		asset: Self::AssetId,
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+		keep_alive: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		match asset {
+			NativeOrAsset::Native => Native::transfer(source, dest, amount, keep_alive),
+			NativeOrAsset::Asset(id) => Assets::transfer(id, source, dest, amount, keep_alive),
+		}
+	}
+}
@@ -5,15 +5,32 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod currency;
+pub use currency::{NativeOrAsset, NativeOrAssets};
+
+pub mod migrations;
+pub mod weights;
+pub use weights::WeightInfo;
+
 pub use pallet::*;
 
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::WeightInfo;
 	use frame_support::{
 		dispatch::DispatchResult,
 		pallet_prelude::*,
-		sp_runtime::traits::Hash,
-		traits::{Currency, ExistenceRequirement, Randomness, ReservableCurrency},
+		sp_runtime::{
+			traits::{Hash, Zero},
+			Permill,
+		},
+		traits::{
+			tokens::{fungibles, DepositConsequence},
+			Currency, Randomness, ReservableCurrency,
+		},
 		transactional,
 	};
 	use frame_system::pallet_prelude::*;
@@ -24,19 +41,25 @@ pub mod pallet {
 	use frame_support::serde::{Deserialize, Serialize};
 
 	type AccountOf<T> = <T as frame_system::Config>::AccountId;
-	type BalanceOf<T> =
-		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	type BalanceOf<T> = <<T as Config>::Currency as fungibles::Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
 	type DepositOf<T> = <<T as Config>::ReservableCurrency as Currency<
 		<T as frame_system::Config>::AccountId,
 	>>::Balance;
+	/// A price: the currency it is denominated in and the amount.
+	type PriceOf<T> = (<T as Config>::AssetId, BalanceOf<T>);
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
-		/// The Currency handler for the kitties pallet.
-		type Currency: Currency<Self::AccountId>;
+		/// The identifier of a currency kitties can be priced in.
+		type AssetId: Parameter + Copy + MaxEncodedLen;
+
+		/// The currencies kitties are priced in, e.g. `NativeOrAssets`.
+		type Currency: fungibles::Transfer<Self::AccountId, AssetId = Self::AssetId>;
 
 		type ReservableCurrency: ReservableCurrency<Self::AccountId>;
 
@@ -48,10 +71,24 @@ pub mod pallet {
 		// The amount required to reserve a kitty.
 		#[pallet::constant]
 		type ReservationFee: Get<DepositOf<Self>>;
+
+		/// The share of the price of every sale withheld by the marketplace.
+		#[pallet::constant]
+		type MarketplaceCut: Get<Permill>;
+
+		/// The account receiving the marketplace cut withheld from sales.
+		type MarketplaceAccount: Get<Self::AccountId>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub (super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::genesis_config]
@@ -87,7 +124,7 @@ pub mod pallet {
 	#[scale_info(skip_type_params(T))]
 	pub struct Kitty<T: Config> {
 		pub dna: [u8; 16],
-		pub price: Option<BalanceOf<T>>,
+		pub price: Option<PriceOf<T>>,
 		pub gender: Gender,
 		pub owner: AccountOf<T>,
 	}
@@ -120,6 +157,7 @@ pub mod pallet {
 		KittyCntOverflow,
 		KittyNotExist,
 		KittyNotForSale,
+		KittyPricedInOtherAsset,
 		TransferToSelf,
 	}
 
@@ -129,17 +167,17 @@ pub mod pallet {
 		/// A new Kitty was successfully created. \[sender, kitty_id, deposit\]
 		Created(T::AccountId, T::Hash, DepositOf<T>),
 		/// Kitty price was successfully set. \[sender, kitty_id, new_price\]
-		PriceSet(T::AccountId, T::Hash, Option<BalanceOf<T>>),
+		PriceSet(T::AccountId, T::Hash, Option<PriceOf<T>>),
 		/// A Kitty was successfully transferred. \[from, to, kitty_id\]
 		Transferred(T::AccountId, T::AccountId, T::Hash),
-		/// A kitty was successfully bought. \[buyer, seller, kitty_id, bid_price\]
-		Bought(T::AccountId, T::AccountId, T::Hash, BalanceOf<T>),
+		/// A kitty was successfully bought. \[buyer, seller, kitty_id, asset, bid_price\]
+		Bought(T::AccountId, T::AccountId, T::Hash, T::AssetId, BalanceOf<T>),
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[transactional]
-		#[pallet::weight(100)]
+		#[pallet::weight(T::WeightInfo::create_kitty(T::MaxKittyOwned::get()))]
 		pub fn create_kitty(origin: OriginFor<T>) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			let kitty_id = Self::mint(&sender, None, None)?;
@@ -147,46 +185,40 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(100)]
+		/// Put the kitty up for sale at `new_price`, an amount of one of the currencies of
+		/// `Config::Currency`, or take it off the market with `None`.
+		#[pallet::weight(T::WeightInfo::set_price())]
 		pub fn set_price(
 			origin: OriginFor<T>,
 			kitty_id: T::Hash,
-			new_price: Option<BalanceOf<T>>,
+			new_price: Option<PriceOf<T>>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			let mut kitty = Self::kitties(&kitty_id).ok_or_else(|| <Error<T>>::KittyNotExist)?;
+			ensure!(kitty.owner == sender, <Error<T>>::NotKittyOwner);
 			kitty.price = new_price;
 			<KittiesOf<T>>::insert(kitty_id, kitty);
 			Self::deposit_event(Event::PriceSet(sender, kitty_id, new_price));
 			Ok(())
 		}
 
-		#[pallet::weight(100)]
+		#[pallet::weight(T::WeightInfo::transfer(T::MaxKittyOwned::get()))]
 		pub fn transfer(
 			origin: OriginFor<T>,
 			to: T::AccountId,
 			kitty_id: T::Hash,
 		) -> DispatchResult {
 			let from = ensure_signed(origin)?;
-			ensure!(Self::is_kitty_owner(&kitty_id, &from)?, <Error<T>>::NotKittyOwner);
-			ensure!(from != to, <Error<T>>::TransferToSelf);
-
-			let to_owned = <KittiesOwned<T>>::get(&to);
-			ensure!(
-				(to_owned.len() as u32) < T::MaxKittyOwned::get(),
-				<Error<T>>::ExceedMaxKittyOwned
-			);
-
-			Self::transfer_kitty_to(&from, &to, &kitty_id)?;
-			Self::deposit_event(Event::Transferred(from, to, kitty_id));
-			Ok(())
+			Self::do_transfer(&from, &to, &kitty_id)
 		}
 
+		/// Buy the kitty for `bid_price` of `asset`, which has to be the currency it is priced in.
 		#[transactional]
-		#[pallet::weight(100)]
+		#[pallet::weight(T::WeightInfo::buy_kitty(T::MaxKittyOwned::get()))]
 		pub fn buy_kitty(
 			origin: OriginFor<T>,
 			kitty_id: T::Hash,
+			asset: T::AssetId,
 			bid_price: BalanceOf<T>,
 		) -> DispatchResult {
 			let buyer = ensure_signed(origin)?;
@@ -194,14 +226,19 @@ pub mod pallet {
 			let kitty = Self::kitties(&kitty_id).ok_or(<Error<T>>::KittyNotExist)?;
 			ensure!(kitty.owner != buyer, <Error<T>>::BuyerIsKittyOwner);
 
-			if let Some(ask_price) = kitty.price {
+			if let Some((ask_asset, ask_price)) = kitty.price {
+				ensure!(ask_asset == asset, <Error<T>>::KittyPricedInOtherAsset);
 				ensure!(ask_price <= bid_price, <Error<T>>::KittyBidPriceTooLow);
 			} else {
 				return Err(<Error<T>>::KittyNotForSale.into());
 			}
 
 			// Check the buyer has enough free balance
-			ensure!(T::Currency::free_balance(&buyer) >= bid_price, <Error<T>>::NotEnoughBalance);
+			ensure!(
+				<T::Currency as fungibles::Inspect<_>>::reducible_balance(asset, &buyer, true) >=
+					bid_price,
+				<Error<T>>::NotEnoughBalance
+			);
 
 			// Make sure the buyer has the capacity to receive one more kitty
 			let buyer_owned = <KittiesOwned<T>>::get(&buyer);
@@ -212,13 +249,37 @@ pub mod pallet {
 
 			let seller = kitty.owner.clone();
 
-			T::Currency::transfer(&buyer, &seller, bid_price, ExistenceRequirement::KeepAlive)?;
+			let marketplace = T::MarketplaceAccount::get();
+			let mut cut = T::MarketplaceCut::get() * bid_price;
+			// A cut the marketplace account cannot receive, e.g. below the minimum balance of the
+			// asset, is left to the seller.
+			if <T::Currency as fungibles::Inspect<_>>::can_deposit(asset, &marketplace, cut) !=
+				DepositConsequence::Success
+			{
+				cut = Zero::zero();
+			}
+			<T::Currency as fungibles::Transfer<_>>::transfer(
+				asset,
+				&buyer,
+				&seller,
+				bid_price - cut,
+				true,
+			)?;
+			if !cut.is_zero() {
+				<T::Currency as fungibles::Transfer<_>>::transfer(
+					asset,
+					&buyer,
+					&marketplace,
+					cut,
+					true,
+				)?;
+			}
 			Self::transfer_kitty_to(&seller, &buyer, &kitty_id)?;
-			Self::deposit_event(Event::Bought(buyer, seller, kitty_id, bid_price));
+			Self::deposit_event(Event::Bought(buyer, seller, kitty_id, asset, bid_price));
 			Ok(())
 		}
 
-		#[pallet::weight(100)]
+		#[pallet::weight(T::WeightInfo::breed_kitty(T::MaxKittyOwned::get()))]
 		pub fn breed_kitty(
 			origin: OriginFor<T>,
 			parent1: T::Hash,
@@ -238,6 +299,25 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
+		fn do_transfer(
+			from: &T::AccountId,
+			to: &T::AccountId,
+			kitty_id: &T::Hash,
+		) -> DispatchResult {
+			ensure!(Self::is_kitty_owner(kitty_id, from)?, <Error<T>>::NotKittyOwner);
+			ensure!(from != to, <Error<T>>::TransferToSelf);
+
+			let to_owned = <KittiesOwned<T>>::get(to);
+			ensure!(
+				(to_owned.len() as u32) < T::MaxKittyOwned::get(),
+				<Error<T>>::ExceedMaxKittyOwned
+			);
+
+			Self::transfer_kitty_to(from, to, kitty_id)?;
+			Self::deposit_event(Event::Transferred(from.clone(), to.clone(), *kitty_id));
+			Ok(())
+		}
+
 		fn gen_gender() -> Gender {
 			let random = T::KittyRandomness::random(&b"gender"[..]).0;
 			match random.as_ref()[0] % 2 {
@@ -261,7 +341,7 @@ pub mod pallet {
 			}
 		}
 
-		fn mint(
+		pub(crate) fn mint(
 			owner: &T::AccountId,
 			dna: Option<[u8; 16]>,
 			gender: Option<Gender>,
@@ -310,7 +390,8 @@ pub mod pallet {
 			.map_err(|_| <Error<T>>::KittyNotExist)?;
 
 			kitty.owner = to.clone();
-			// Reset the price, so the kitty is not for sale until `set_price` is called by new owner
+			// Reset the price, so the kitty is not for sale until `set_price` is called by new
+			// owner
 			kitty.price = None;
 
 			// Append the kitty to new owners owned vec
@@ -0,0 +1,56 @@
+//! Storage migrations for pallet-kitties.
+
+use crate::pallet::{Config, Gender, KittiesOf, Kitty, Pallet};
+use codec::Decode;
+use frame_support::{
+	traits::{tokens::fungibles, Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+
+/// Moves the prices set before kitties could be priced in assets to the default currency, which
+/// is the native one for `NativeOrAsset`.
+pub mod v1 {
+	use super::*;
+
+	type BalanceOf<T> = <<T as Config>::Currency as fungibles::Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	/// A kitty as stored before version 1, priced in the native currency.
+	#[derive(Decode)]
+	struct OldKitty<T: Config> {
+		dna: [u8; 16],
+		price: Option<BalanceOf<T>>,
+		gender: Gender,
+		owner: T::AccountId,
+	}
+
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T>
+	where
+		T::AssetId: Default,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut count: Weight = 0;
+			KittiesOf::<T>::translate::<OldKitty<T>, _>(|_, old| {
+				count += 1;
+				Some(Kitty {
+					dna: old.dna,
+					price: old.price.map(|amount| (T::AssetId::default(), amount)),
+					gender: old.gender,
+					owner: old.owner,
+				})
+			});
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(target: "runtime::kitties", "Migrated {} kitties to v1", count);
+
+			T::DbWeight::get().reads_writes(count + 1, count + 1)
+		}
+	}
+}
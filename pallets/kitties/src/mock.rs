@@ -1,13 +1,13 @@
 use crate as pallet_kitties;
+use crate::{NativeOrAsset, NativeOrAssets};
 
-use frame_support::{
-	parameter_types,
-	traits::{OnInitialize},
-};
+use frame_support::{assert_ok, parameter_types, traits::OnInitialize};
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, Header as _, IdentityLookup},
+	Permill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -22,6 +22,7 @@ frame_support::construct_runtime!(
 		System: frame_system,
 		RandomnessCollectiveFlip: pallet_randomness_collective_flip,
 		Balances: pallet_balances,
+		Assets: pallet_assets,
 		Kitties: pallet_kitties,
 	}
 );
@@ -75,21 +76,64 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const AssetDeposit: u64 = 0;
+	pub const ApprovalDeposit: u64 = 0;
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: u64 = 0;
+	pub const MetadataDepositPerByte: u64 = 0;
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = u64;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<u64>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
 impl pallet_randomness_collective_flip::Config for Test {}
 
 parameter_types! {
 	pub const ReservationFee: u64 = 5;
 	pub const MaxKittyOwned: u32 = 3;
+	pub const MarketplaceCut: Permill = Permill::from_percent(10);
+	pub const MarketplaceAccount: u64 = 99;
 }
 
 impl pallet_kitties::Config for Test {
 	type Event = Event;
-	type Currency = Balances;
+	type AssetId = NativeOrAsset<u32>;
+	type Currency = NativeOrAssets<Balances, Assets>;
 	type ReservableCurrency = Balances;
 	type KittyRandomness = RandomnessCollectiveFlip;
 
 	type MaxKittyOwned = MaxKittyOwned;
 	type ReservationFee = ReservationFee;
+	type MarketplaceCut = MarketplaceCut;
+	type MarketplaceAccount = MarketplaceAccount;
+	type WeightInfo = ();
+}
+
+/// The native currency.
+pub const NATIVE: NativeOrAsset<u32> = NativeOrAsset::Native;
+
+/// The asset of the game token.
+pub const GAME_TOKEN: NativeOrAsset<u32> = NativeOrAsset::Asset(0);
+
+/// Create the game token, with a minimum balance of 2, and give 50 of it to accounts 1 and 2.
+pub fn create_game_token() {
+	assert_ok!(Assets::force_create(Origin::root(), 0, 1, true, 2));
+	assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 50));
+	assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 50));
 }
 
 // Build genesis storage according to the mock runtime.
@@ -1,7 +1,10 @@
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
 use sp_core::H256;
 
-use crate::{mock::*, Error};
+use crate::{migrations, mock::*, Error, Gender, KittiesOf, NativeOrAsset};
 
 #[test]
 fn create_kitty_should_work() {
@@ -39,7 +42,7 @@ fn set_price_should_work() {
 		assert_ok!(Kitties::create_kitty(Origin::signed(1)));
 		let kitties = Kitties::kittes_owned(&1);
 		assert_eq!(kitties.len(), 1);
-		assert_ok!(Kitties::set_price(Origin::signed(1), kitties[0], Some(1)));
+		assert_ok!(Kitties::set_price(Origin::signed(1), kitties[0], Some((NATIVE, 1))));
 	});
 }
 
@@ -47,12 +50,26 @@ fn set_price_should_work() {
 fn should_return_kitty_not_exist_error_when_set_price() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			Kitties::set_price(Origin::signed(1), H256::zero(), Some(1)),
+			Kitties::set_price(Origin::signed(1), H256::zero(), Some((NATIVE, 1))),
 			<Error<Test>>::KittyNotExist
 		);
 	});
 }
 
+#[test]
+fn should_return_not_kitty_owner_error_when_set_price() {
+	new_test_ext().execute_with(|| {
+		setup_blocks(10);
+		assert_ok!(Kitties::create_kitty(Origin::signed(1)));
+		let kitties = Kitties::kittes_owned(&1);
+		assert_eq!(kitties.len(), 1);
+		assert_noop!(
+			Kitties::set_price(Origin::signed(2), kitties[0], Some((NATIVE, 1))),
+			<Error<Test>>::NotKittyOwner
+		);
+	});
+}
+
 #[test]
 fn transfer_kitty_should_work() {
 	new_test_ext().execute_with(|| {
@@ -111,8 +128,8 @@ fn buy_kitty_should_work() {
 		assert_ok!(Kitties::create_kitty(Origin::signed(1)));
 		let kitties = Kitties::kittes_owned(&1);
 		assert_eq!(kitties.len(), 1);
-		assert_ok!(Kitties::set_price(Origin::signed(1), kitties[0], Some(1)));
-		assert_ok!(Kitties::buy_kitty(Origin::signed(2), kitties[0], 2));
+		assert_ok!(Kitties::set_price(Origin::signed(1), kitties[0], Some((NATIVE, 1))));
+		assert_ok!(Kitties::buy_kitty(Origin::signed(2), kitties[0], NATIVE, 2));
 	});
 }
 
@@ -120,7 +137,7 @@ fn buy_kitty_should_work() {
 fn should_return_kitty_not_exist_error_when_buy_kitty() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			Kitties::buy_kitty(Origin::signed(1), H256::zero(), 2),
+			Kitties::buy_kitty(Origin::signed(1), H256::zero(), NATIVE, 2),
 			<Error<Test>>::KittyNotExist
 		);
 	});
@@ -134,7 +151,7 @@ fn should_return_kitty_not_for_sale_error_when_buy_kitty() {
 		let kitties = Kitties::kittes_owned(&1);
 		assert_eq!(kitties.len(), 1);
 		assert_noop!(
-			Kitties::buy_kitty(Origin::signed(2), kitties[0], 2),
+			Kitties::buy_kitty(Origin::signed(2), kitties[0], NATIVE, 2),
 			<Error<Test>>::KittyNotForSale
 		);
 	});
@@ -147,9 +164,9 @@ fn should_return_kitty_bid_price_too_low_error_when_buy_kitty() {
 		assert_ok!(Kitties::create_kitty(Origin::signed(1)));
 		let kitties = Kitties::kittes_owned(&1);
 		assert_eq!(kitties.len(), 1);
-		assert_ok!(Kitties::set_price(Origin::signed(1), kitties[0], Some(3)));
+		assert_ok!(Kitties::set_price(Origin::signed(1), kitties[0], Some((NATIVE, 3))));
 		assert_noop!(
-			Kitties::buy_kitty(Origin::signed(2), kitties[0], 2),
+			Kitties::buy_kitty(Origin::signed(2), kitties[0], NATIVE, 2),
 			<Error<Test>>::KittyBidPriceTooLow
 		);
 	});
@@ -162,9 +179,9 @@ fn should_return_not_enough_balance_error_when_buy_kitty() {
 		assert_ok!(Kitties::create_kitty(Origin::signed(1)));
 		let kitties = Kitties::kittes_owned(&1);
 		assert_eq!(kitties.len(), 1);
-		assert_ok!(Kitties::set_price(Origin::signed(1), kitties[0], Some(1)));
+		assert_ok!(Kitties::set_price(Origin::signed(1), kitties[0], Some((NATIVE, 1))));
 		assert_noop!(
-			Kitties::buy_kitty(Origin::signed(3), kitties[0], 3),
+			Kitties::buy_kitty(Origin::signed(3), kitties[0], NATIVE, 3),
 			<Error<Test>>::NotEnoughBalance
 		);
 	});
@@ -213,3 +230,115 @@ fn should_return_kitty_not_exist_error_when_breed_kitty() {
 		);
 	});
 }
+
+#[test]
+fn buy_kitty_should_withhold_marketplace_cut() {
+	new_test_ext().execute_with(|| {
+		setup_blocks(10);
+		assert_ok!(Kitties::create_kitty(Origin::signed(2)));
+		let kitties = Kitties::kittes_owned(&2);
+		assert_ok!(Kitties::set_price(Origin::signed(2), kitties[0], Some((NATIVE, 10))));
+
+		assert_ok!(Kitties::buy_kitty(Origin::signed(1), kitties[0], NATIVE, 10));
+		assert_eq!(Balances::free_balance(1), 10);
+		// The seller receives the price minus the 10% cut, which goes to the marketplace.
+		assert_eq!(Balances::free_balance(2), 14);
+		assert_eq!(Balances::free_balance(MarketplaceAccount::get()), 1);
+	});
+}
+
+#[test]
+fn buy_kitty_should_work_in_an_asset() {
+	new_test_ext().execute_with(|| {
+		setup_blocks(10);
+		create_game_token();
+		assert_ok!(Kitties::create_kitty(Origin::signed(2)));
+		let kitties = Kitties::kittes_owned(&2);
+		assert_ok!(Kitties::set_price(Origin::signed(2), kitties[0], Some((GAME_TOKEN, 20))));
+
+		assert_ok!(Kitties::buy_kitty(Origin::signed(1), kitties[0], GAME_TOKEN, 20));
+		assert_eq!(Kitties::kittes_owned(&1).len(), 1);
+		assert_eq!(Assets::balance(0, 1), 30);
+		// The seller receives the price minus the 10% cut, which goes to the marketplace.
+		assert_eq!(Assets::balance(0, 2), 68);
+		assert_eq!(Assets::balance(0, MarketplaceAccount::get()), 2);
+		// Only the reservation fee was taken from the native balances.
+		assert_eq!(Balances::free_balance(1), 20);
+		assert_eq!(Balances::free_balance(2), 5);
+	});
+}
+
+#[test]
+fn buy_kitty_should_leave_cuts_below_minimum_balance_to_the_seller() {
+	new_test_ext().execute_with(|| {
+		setup_blocks(10);
+		create_game_token();
+		assert_ok!(Kitties::create_kitty(Origin::signed(2)));
+		let kitties = Kitties::kittes_owned(&2);
+		assert_ok!(Kitties::set_price(Origin::signed(2), kitties[0], Some((GAME_TOKEN, 10))));
+
+		// The cut of 1 is below the minimum balance of 2 of the game token.
+		assert_ok!(Kitties::buy_kitty(Origin::signed(1), kitties[0], GAME_TOKEN, 10));
+		assert_eq!(Assets::balance(0, 2), 60);
+		assert_eq!(Assets::balance(0, MarketplaceAccount::get()), 0);
+	});
+}
+
+#[test]
+fn should_return_kitty_priced_in_other_asset_error_when_buy_kitty() {
+	new_test_ext().execute_with(|| {
+		setup_blocks(10);
+		create_game_token();
+		assert_ok!(Kitties::create_kitty(Origin::signed(2)));
+		let kitties = Kitties::kittes_owned(&2);
+		assert_ok!(Kitties::set_price(Origin::signed(2), kitties[0], Some((GAME_TOKEN, 1))));
+		assert_noop!(
+			Kitties::buy_kitty(Origin::signed(1), kitties[0], NATIVE, 1),
+			<Error<Test>>::KittyPricedInOtherAsset
+		);
+	});
+}
+
+#[test]
+fn should_return_not_enough_balance_error_when_buy_kitty_in_an_asset() {
+	new_test_ext().execute_with(|| {
+		setup_blocks(10);
+		create_game_token();
+		assert_ok!(Kitties::create_kitty(Origin::signed(2)));
+		let kitties = Kitties::kittes_owned(&2);
+		assert_ok!(Kitties::set_price(Origin::signed(2), kitties[0], Some((GAME_TOKEN, 10))));
+		// Account 3 holds none of the game token.
+		assert_noop!(
+			Kitties::buy_kitty(Origin::signed(3), kitties[0], GAME_TOKEN, 10),
+			<Error<Test>>::NotEnoughBalance
+		);
+	});
+}
+
+#[test]
+fn migration_to_v1_should_price_kitties_in_the_native_currency() {
+	new_test_ext().execute_with(|| {
+		setup_blocks(10);
+		StorageVersion::new(0).put::<Kitties>();
+		assert_ok!(Kitties::create_kitty(Origin::signed(1)));
+		assert_ok!(Kitties::create_kitty(Origin::signed(1)));
+		let kitties = Kitties::kittes_owned(&1);
+		// Kitties were priced as a plain native balance before version 1.
+		for (kitty_id, price) in kitties.iter().zip([Some(8u64), None]) {
+			let kitty = Kitties::kitties(kitty_id).unwrap();
+			let old = (kitty.dna, price, Gender::Male, 1u64);
+			frame_support::storage::unhashed::put(
+				&KittiesOf::<Test>::hashed_key_for(kitty_id),
+				&old,
+			);
+		}
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(Kitties::on_chain_storage_version(), 1);
+		let migrated = Kitties::kitties(&kitties[0]).unwrap();
+		assert_eq!(migrated.price, Some((NativeOrAsset::Native, 8)));
+		assert_eq!(migrated.gender, Gender::Male);
+		assert_eq!(migrated.owner, 1);
+		assert_eq!(Kitties::kitties(&kitties[1]).unwrap().price, None);
+	});
+}
@@ -0,0 +1,108 @@
+//! Weights for pallet_kitties
+//!
+//! These are estimates pending a run of the Substrate benchmark CLI on reference hardware.
+//! Regenerate this file with:
+//!
+//! ./target/release/node-template benchmark --chain=dev --steps=50 --repeat=20
+//! --pallet=pallet_kitties --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --heap-pages=4096 --output=./pallets/kitties/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_kitties.
+pub trait WeightInfo {
+	fn create_kitty(n: u32, ) -> Weight;
+	fn set_price() -> Weight;
+	fn transfer(n: u32, ) -> Weight;
+	fn buy_kitty(n: u32, ) -> Weight;
+	fn breed_kitty(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_kitties using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: System Account (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: Kitties KittyCnt (r:1 w:1)
+	// Storage: Kitties KittiesOwned (r:1 w:1)
+	// Storage: Kitties KittiesOf (r:0 w:1)
+	fn create_kitty(n: u32, ) -> Weight {
+		(46_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Kitties KittiesOf (r:1 w:1)
+	fn set_price() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Kitties KittiesOf (r:1 w:1)
+	// Storage: Kitties KittiesOwned (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn transfer(n: u32, ) -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add((250_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Kitties KittiesOf (r:1 w:1)
+	// Storage: Kitties KittiesOwned (r:2 w:2)
+	// Storage: System Account (r:3 w:3)
+	fn buy_kitty(n: u32, ) -> Weight {
+		(97_000_000 as Weight)
+			.saturating_add((250_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Kitties KittiesOf (r:2 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: Kitties KittyCnt (r:1 w:1)
+	// Storage: Kitties KittiesOwned (r:1 w:1)
+	fn breed_kitty(n: u32, ) -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_kitty(n: u32, ) -> Weight {
+		(46_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn set_price() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn transfer(n: u32, ) -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add((250_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn buy_kitty(n: u32, ) -> Weight {
+		(97_000_000 as Weight)
+			.saturating_add((250_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn breed_kitty(n: u32, ) -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+}
@@ -0,0 +1,152 @@
+//! RPC interface for the proof of existence pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_poe_runtime_api::{ClaimDetails, ClaimInfo};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_poe_runtime_api::PoeApi as PoeRuntimeApi;
+
+/// Error code returned when a runtime API call fails.
+const RUNTIME_ERROR: i64 = 1;
+
+/// Proof of existence RPC methods.
+#[rpc]
+pub trait PoeApi<BlockHash, AccountId, BlockNumber> {
+	/// The proof stored for `claim`, if any.
+	#[rpc(name = "poe_claim")]
+	fn claim(
+		&self,
+		claim: Bytes,
+		at: Option<BlockHash>,
+	) -> Result<Option<ClaimInfo<AccountId, BlockNumber>>>;
+
+	/// The proof stored for `claim`, if any, along with the display name of its owner.
+	#[rpc(name = "poe_claimDetails")]
+	fn claim_details(
+		&self,
+		claim: Bytes,
+		at: Option<BlockHash>,
+	) -> Result<Option<ClaimDetails<AccountId, BlockNumber>>>;
+
+	/// A batch of proofs owned by `owner`, following the proof `after` or from the first one.
+	#[rpc(name = "poe_claimsOf")]
+	fn claims_of(
+		&self,
+		owner: AccountId,
+		after: Option<Bytes>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<Bytes>>;
+
+	/// The proof for `claim` if it exists and has not expired yet.
+	#[rpc(name = "poe_verify")]
+	fn verify(
+		&self,
+		claim: Bytes,
+		at: Option<BlockHash>,
+	) -> Result<Option<ClaimInfo<AccountId, BlockNumber>>>;
+
+	/// Every version of the document `claim` belongs to, oldest first.
+	#[rpc(name = "poe_revisionHistory")]
+	fn revision_history(&self, claim: Bytes, at: Option<BlockHash>) -> Result<Vec<Bytes>>;
+}
+
+/// Implements the [`PoeApi`] RPC trait by calling into the runtime.
+pub struct Poe<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Poe<C, Block> {
+	/// Create a new `Poe` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, BlockNumber> PoeApi<<Block as BlockT>::Hash, AccountId, BlockNumber>
+	for Poe<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: PoeRuntimeApi<Block, AccountId, BlockNumber>,
+	AccountId: Codec,
+	BlockNumber: Codec,
+{
+	fn claim(
+		&self,
+		claim: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ClaimInfo<AccountId, BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.claim(&at, claim.to_vec())
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn claim_details(
+		&self,
+		claim: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ClaimDetails<AccountId, BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.claim_details(&at, claim.to_vec())
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn claims_of(
+		&self,
+		owner: AccountId,
+		after: Option<Bytes>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<Bytes>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.claims_of(&at, owner, after.map(|after| after.to_vec()))
+			.map(|claims| claims.into_iter().map(Bytes).collect())
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn verify(
+		&self,
+		claim: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ClaimInfo<AccountId, BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.verify(&at, claim.to_vec())
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn revision_history(
+		&self,
+		claim: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<Bytes>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.revision_history(&at, claim.to_vec())
+			.map(|claims| claims.into_iter().map(Bytes).collect())
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime error".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
@@ -0,0 +1,30 @@
+//! Runtime API definition for the proof of existence pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+pub use pallet_poe::{ClaimDetails, ClaimInfo};
+
+sp_api::decl_runtime_apis! {
+	pub trait PoeApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// The proof stored for `claim`, if any.
+		fn claim(claim: Vec<u8>) -> Option<ClaimInfo<AccountId, BlockNumber>>;
+
+		/// The proof stored for `claim`, if any, along with the display name of its owner.
+		fn claim_details(claim: Vec<u8>) -> Option<ClaimDetails<AccountId, BlockNumber>>;
+
+		/// A batch of proofs owned by `owner`, following the proof `after` or from the first one.
+		fn claims_of(owner: AccountId, after: Option<Vec<u8>>) -> Vec<Vec<u8>>;
+
+		/// The proof for `claim` if it exists and has not expired yet.
+		fn verify(claim: Vec<u8>) -> Option<ClaimInfo<AccountId, BlockNumber>>;
+
+		/// Every version of the document `claim` belongs to, oldest first.
+		fn revision_history(claim: Vec<u8>) -> Vec<Vec<u8>>;
+	}
+}
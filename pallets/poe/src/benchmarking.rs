@@ -0,0 +1,268 @@
+//! Benchmarking setup for pallet-poe
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Poe;
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::{Currency, EnsureOrigin, Hooks},
+};
+use frame_system::{
+	offchain::{AppCrypto, SigningTypes},
+	RawOrigin,
+};
+use sp_runtime::{traits::IdentifyAccount, RuntimeAppPublic};
+use sp_std::{prelude::*, vec};
+
+fn expiry<T: Config>() -> Option<T::BlockNumber> {
+	Some(frame_system::Pallet::<T>::block_number() + 10u32.into())
+}
+
+fn signers<T: Config>(n: u32) -> Vec<T::AccountId> {
+	(0..n).map(|i| account("signer", i, 0)).collect()
+}
+
+/// Give `who` enough to reserve a few dispute bonds.
+fn fund<T: Config>(who: &T::AccountId) {
+	let balance = T::Currency::minimum_balance() + T::DisputeBond::get() * 10u32.into();
+	T::Currency::make_free_balance_be(who, balance);
+}
+
+/// Dispute `claim` from a new, funded account.
+fn dispute<T: Config>(claim: &Vec<u8>) -> DispatchResult {
+	let disputer: T::AccountId = account("disputer", 0, 0);
+	fund::<T>(&disputer);
+	Poe::<T>::dispute_claim(RawOrigin::Signed(disputer).into(), claim.clone(), Default::default())
+}
+
+/// Create `claim` owned by `owner` as a revision of `parent`, revised in turn by `successor`,
+/// and let it expire at `at`. Removing it cuts the revision history on both sides.
+fn revised_claim<T: Config>(
+	owner: &T::AccountId,
+	parent: Vec<u8>,
+	claim: Vec<u8>,
+	successor: Vec<u8>,
+	at: T::BlockNumber,
+) -> DispatchResult {
+	let origin = || -> T::Origin { RawOrigin::Signed(owner.clone()).into() };
+	Poe::<T>::create_claim(origin(), parent.clone(), None)?;
+	Poe::<T>::create_revision(origin(), parent, claim.clone())?;
+	Poe::<T>::create_revision(origin(), claim.clone(), successor)?;
+	Poe::<T>::renew_claim(origin(), claim, Some(at))
+}
+
+type PublicOf<T> = <T as SigningTypes>::Public;
+type SignatureOf<T> = <T as SigningTypes>::Signature;
+type CryptoOf<T> = <T as Config>::AuthorityId;
+type AppPublicOf<T> = <CryptoOf<T> as AppCrypto<PublicOf<T>, SignatureOf<T>>>::RuntimeAppPublic;
+type GenericPublicOf<T> = <CryptoOf<T> as AppCrypto<PublicOf<T>, SignatureOf<T>>>::GenericPublic;
+
+/// A new key of `AuthorityId`, to sign attestations with, and its account.
+fn signer<T: Config>() -> (PublicOf<T>, T::AccountId) {
+	let key = AppPublicOf::<T>::generate_pair(None);
+	let public: PublicOf<T> = GenericPublicOf::<T>::from(key).into();
+	(public.clone(), public.into_account())
+}
+
+benchmarks! {
+	where_clause { where T::OffchainSignature: From<SignatureOf<T>> }
+
+	create_claim {
+		let s in 1 .. T::MaxSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let claim = vec![0u8; s as usize];
+	}: _(RawOrigin::Signed(caller.clone()), claim.clone(), expiry::<T>())
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).map(|info| info.owner), Some(caller));
+	}
+
+	revoke_claim {
+		let s in 1 .. T::MaxSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let (parent, successor) = (vec![1u8; s as usize], vec![2u8; s as usize]);
+		let claim = vec![0u8; s as usize];
+		revised_claim::<T>(&caller, parent, claim.clone(), successor, expiry::<T>().unwrap())?;
+	}: _(RawOrigin::Signed(caller), claim.clone())
+	verify {
+		assert!(Proofs::<T>::get(&claim).is_none());
+	}
+
+	transfer_claim {
+		let s in 1 .. T::MaxSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		let claim = vec![0u8; s as usize];
+		let origin = RawOrigin::Signed(caller.clone());
+		Poe::<T>::create_claim(origin.into(), claim.clone(), expiry::<T>())?;
+	}: _(RawOrigin::Signed(caller), recipient.clone(), claim.clone())
+	verify {
+		assert_eq!(PendingTransfers::<T>::get(&claim), Some(recipient));
+	}
+
+	accept_claim {
+		let s in 1 .. T::MaxSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let claim = vec![0u8; s as usize];
+		let origin = RawOrigin::Signed(owner.clone());
+		Poe::<T>::create_claim(origin.clone().into(), claim.clone(), expiry::<T>())?;
+		Poe::<T>::transfer_claim(origin.into(), caller.clone(), claim.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), claim.clone())
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).map(|info| info.owner), Some(caller));
+	}
+
+	renew_claim {
+		let s in 1 .. T::MaxSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let claim = vec![0u8; s as usize];
+		let origin = RawOrigin::Signed(caller.clone());
+		Poe::<T>::create_claim(origin.into(), claim.clone(), expiry::<T>())?;
+		let expires_at = Some(frame_system::Pallet::<T>::block_number() + 20u32.into());
+	}: _(RawOrigin::Signed(caller), claim.clone(), expires_at)
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).and_then(|info| info.expires_at), expires_at);
+	}
+
+	create_revision {
+		let s in 1 .. T::MaxSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let (parent, claim) = (vec![1u8; s as usize], vec![0u8; s as usize]);
+		let origin = RawOrigin::Signed(caller.clone());
+		Poe::<T>::create_claim(origin.into(), parent.clone(), None)?;
+	}: _(RawOrigin::Signed(caller), parent.clone(), claim.clone())
+	verify {
+		assert_eq!(Successors::<T>::get(&parent), Some(claim));
+	}
+
+	create_cosigned_claim {
+		let s in 1 .. T::MaxSize::get();
+		let n in 1 .. T::MaxCoSigners::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let claim = vec![0u8; s as usize];
+		let signers = signers::<T>(n);
+	}: _(RawOrigin::Signed(caller), claim.clone(), signers, n)
+	verify {
+		assert!(PendingClaims::<T>::contains_key(&claim));
+	}
+
+	approve_claim {
+		let s in 1 .. T::MaxSize::get();
+		let creator: T::AccountId = whitelisted_caller();
+		let claim = vec![0u8; s as usize];
+		let mut signers = signers::<T>(T::MaxCoSigners::get());
+		let origin = RawOrigin::Signed(creator.clone());
+		let threshold = signers.len() as u32;
+		Poe::<T>::create_cosigned_claim(origin.into(), claim.clone(), signers.clone(), threshold)?;
+		// The last approval reaches the threshold and finalizes the claim.
+		let last = signers.pop().unwrap();
+		for signer in signers {
+			Poe::<T>::approve_claim(RawOrigin::Signed(signer).into(), claim.clone())?;
+		}
+	}: _(RawOrigin::Signed(last), claim.clone())
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).map(|info| info.owner), Some(creator));
+	}
+
+	attest_on_behalf {
+		let s in 1 .. T::MaxSize::get();
+		let relayer: T::AccountId = whitelisted_caller();
+		let claim = vec![0u8; s as usize];
+		let (public, signer) = signer::<T>();
+		let payload = Poe::<T>::attest_payload(&claim, 0);
+		let signature = CryptoOf::<T>::sign(&payload, public).ok_or("signing failed")?;
+	}: _(RawOrigin::Signed(relayer), claim.clone(), signer.clone(), signature.into())
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).map(|info| info.owner), Some(signer));
+	}
+
+	dispute_claim {
+		let s in 1 .. T::MaxSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let claim = vec![0u8; s as usize];
+		Poe::<T>::create_claim(RawOrigin::Signed(owner).into(), claim.clone(), expiry::<T>())?;
+		fund::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), claim.clone(), T::Hash::default())
+	verify {
+		assert_eq!(Disputes::<T>::get(&claim).map(|dispute| dispute.disputer), Some(caller));
+	}
+
+	defend_claim {
+		let s in 1 .. T::MaxSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let claim = vec![0u8; s as usize];
+		let origin = RawOrigin::Signed(caller.clone());
+		Poe::<T>::create_claim(origin.into(), claim.clone(), expiry::<T>())?;
+		dispute::<T>(&claim)?;
+		fund::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), claim.clone(), T::Hash::default())
+	verify {
+		assert_eq!(Disputes::<T>::get(&claim).and_then(|dispute| dispute.defender), Some(caller));
+	}
+
+	resolve_dispute {
+		let s in 1 .. T::MaxSize::get();
+		let owner: T::AccountId = whitelisted_caller();
+		let (parent, successor) = (vec![1u8; s as usize], vec![2u8; s as usize]);
+		let claim = vec![0u8; s as usize];
+		revised_claim::<T>(&owner, parent, claim.clone(), successor, expiry::<T>().unwrap())?;
+		let origin = RawOrigin::Signed(owner.clone());
+		dispute::<T>(&claim)?;
+		fund::<T>(&owner);
+		Poe::<T>::defend_claim(origin.into(), claim.clone(), T::Hash::default())?;
+		// Striking the claim removes it and slashes the defender.
+		let resolve_origin = T::ResolveOrigin::successful_origin();
+	}: _<T::Origin>(resolve_origin, claim.clone(), false)
+	verify {
+		assert!(Proofs::<T>::get(&claim).is_none());
+	}
+
+	cancel_transfer {
+		let s in 1 .. T::MaxSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		let claim = vec![0u8; s as usize];
+		let origin = RawOrigin::Signed(caller.clone());
+		Poe::<T>::create_claim(origin.clone().into(), claim.clone(), expiry::<T>())?;
+		Poe::<T>::transfer_claim(origin.into(), recipient, claim.clone())?;
+	}: _(RawOrigin::Signed(caller), claim.clone())
+	verify {
+		assert!(PendingTransfers::<T>::get(&claim).is_none());
+	}
+
+	anchor_batch {
+		let origin = T::AnchorOrigin::successful_origin();
+		let root = T::Hash::default();
+	}: _<T::Origin>(origin, root, 100)
+	verify {
+		assert!(Anchors::<T>::contains_key(&root));
+	}
+
+	on_initialize {
+		let e in 0 .. T::MaxExpiringPerBlock::get();
+		let p in 0 .. T::MaxExpiringPerBlock::get();
+		let owner: T::AccountId = whitelisted_caller();
+		let at = frame_system::Pallet::<T>::block_number() + T::PendingClaimWindow::get();
+		for i in 0 .. e {
+			let (parent, successor) = ((1u8, i).encode(), (2u8, i).encode());
+			revised_claim::<T>(&owner, parent, (0u8, i).encode(), successor, at)?;
+		}
+		for i in 0 .. p {
+			let origin = RawOrigin::Signed(owner.clone());
+			Poe::<T>::create_cosigned_claim(origin.into(), (3u8, i).encode(), signers::<T>(1), 1)?;
+		}
+	}: {
+		Poe::<T>::on_initialize(at);
+	}
+	verify {
+		// Only the parents and successors of the expired claims are left.
+		assert_eq!(ClaimsOwned::<T>::iter_prefix(&owner).count() as u32, 2 * e);
+		assert_eq!(PendingClaims::<T>::iter().count(), 0);
+	}
+
+	impl_benchmark_test_suite!(Poe, crate::mock::new_test_ext(), crate::mock::Test);
+}
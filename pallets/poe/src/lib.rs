@@ -7,29 +7,319 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod weights;
+pub use weights::WeightInfo;
+
+use sp_core::crypto::KeyTypeId;
+
+/// Provides the names under which the owners of claims are known.
+pub trait IdentityProvider<AccountId> {
+	/// The display name of `who`, if a registrar judged its identity.
+	fn display_name(who: &AccountId) -> Option<sp_std::vec::Vec<u8>>;
+}
+
+impl<AccountId> IdentityProvider<AccountId> for () {
+	fn display_name(_: &AccountId) -> Option<sp_std::vec::Vec<u8>> {
+		None
+	}
+}
+
+/// The key type of the keys the offchain worker signs `anchor_batch` transactions with.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"poe!");
+
+/// The crypto of the keys of type [`KEY_TYPE`].
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// The `AppCrypto` of runtimes signing with `MultiSignature`.
+	pub struct AnchorAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for AnchorAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
 /// A module for proof of existence
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
-	use frame_system::pallet_prelude::*;
+	use crate::{IdentityProvider, WeightInfo};
+	use frame_support::{
+		dispatch::DispatchResult,
+		pallet_prelude::*,
+		sp_runtime::traits::{IdentifyAccount, Verify, Zero},
+		traits::{Currency, OnUnbalanced, ReservableCurrency},
+	};
+	use frame_system::{
+		offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer},
+		pallet_prelude::*,
+	};
+	use scale_info::TypeInfo;
+	use sp_runtime::{
+		offchain::{http, Duration},
+		traits::Hash,
+	};
 	use sp_std::prelude::*;
 
+	#[cfg(feature = "std")]
+	use frame_support::serde::{Deserialize, Serialize};
+
+	/// The number of claims returned per page by `claims_page`.
+	pub const CLAIMS_PER_PAGE: u32 = 50;
+
+	/// The local endpoint the offchain worker pulls batches of hashes to anchor from. It answers
+	/// with the concatenated SCALE encoding of the hashes.
+	pub const ANCHOR_ENDPOINT: &str = "http://localhost:8090/poe/batch";
+
+	/// How long the offchain worker waits for `ANCHOR_ENDPOINT` to answer.
+	const ANCHOR_TIMEOUT_MS: u64 = 2_000;
+
+	type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	/// Everything recorded on chain about a single proof.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Deserialize, Serialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct ClaimInfo<AccountId, BlockNumber> {
+		/// The account owning the proof.
+		pub owner: AccountId,
+		/// The block in which the proof was created.
+		pub created_at: BlockNumber,
+		/// The block in which the proof is pruned, if any.
+		pub expires_at: Option<BlockNumber>,
+	}
+
+	pub type ClaimInfoOf<T> =
+		ClaimInfo<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+
+	/// A proof along with the name its owner is known under.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Deserialize, Serialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct ClaimDetails<AccountId, BlockNumber> {
+		/// The proof itself.
+		#[cfg_attr(feature = "std", serde(flatten))]
+		pub info: ClaimInfo<AccountId, BlockNumber>,
+		/// The display name of the owner, if a registrar judged its identity.
+		pub owner_display_name: Option<Vec<u8>>,
+	}
+
+	pub type ClaimDetailsOf<T> = ClaimDetails<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// A claim which only becomes a proof once enough of its co-signers approved it.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct PendingClaim<T: Config> {
+		/// The account which will own the proof once it is finalized.
+		pub creator: T::AccountId,
+		/// The accounts allowed to approve the claim.
+		pub signers: BoundedVec<T::AccountId, T::MaxCoSigners>,
+		/// The number of approvals needed to finalize the claim.
+		pub threshold: u32,
+		/// The signers which approved the claim so far.
+		pub approvals: BoundedVec<T::AccountId, T::MaxCoSigners>,
+		/// The block in which the claim is dropped if still pending.
+		pub deadline: T::BlockNumber,
+	}
+
+	/// A batch of external hashes anchored by its Merkle root.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct AnchorInfo<AccountId, BlockNumber> {
+		/// The account which submitted the root.
+		pub submitter: AccountId,
+		/// The block in which the root was anchored.
+		pub anchored_at: BlockNumber,
+		/// The number of hashes in the batch.
+		pub leaves: u32,
+	}
+
+	type AnchorInfoOf<T> = AnchorInfo<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// The place of a claim in the revision history of a document.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct Revision {
+		/// The claim this one is a new version of, or `None` once that claim was removed.
+		pub parent: Option<Vec<u8>>,
+		/// The number of revisions since the original claim, starting from 1.
+		pub version: u32,
+	}
+
+	/// An open challenge of the authorship of a claim.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Dispute<T: Config> {
+		/// The account contesting the claim.
+		pub disputer: T::AccountId,
+		/// The hash of the disputer's off-chain evidence.
+		pub evidence: T::Hash,
+		/// The bond reserved from the disputer, and from the defender if any.
+		pub bond: BalanceOf<T>,
+		/// The claim owner, once they matched the bond to defend the claim.
+		pub defender: Option<T::AccountId>,
+	}
+
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
 		#[pallet::constant]
 		type MaxSize: Get<u32>;
+
+		/// The maximum number of claims expiring in the same block, which bounds the work done
+		/// by `on_initialize`.
+		#[pallet::constant]
+		type MaxExpiringPerBlock: Get<u32>;
+
+		/// The maximum number of revisions of a single document.
+		#[pallet::constant]
+		type MaxRevisions: Get<u32>;
+
+		/// The maximum number of co-signers a claim may name.
+		#[pallet::constant]
+		type MaxCoSigners: Get<u32>;
+
+		/// The number of blocks co-signers have to approve a claim before it is dropped.
+		#[pallet::constant]
+		type PendingClaimWindow: Get<Self::BlockNumber>;
+
+		/// The signature type used by `attest_on_behalf`.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+		/// The public key of an `OffchainSignature`, identifying the owner of an attested claim.
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// The currency in which dispute bonds are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The bond reserved from each party to a dispute.
+		#[pallet::constant]
+		type DisputeBond: Get<BalanceOf<Self>>;
+
+		/// The origin which may uphold or strike a disputed claim.
+		type ResolveOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Handler for the bond slashed from the losing party of a dispute.
+		type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// The key the offchain worker signs `anchor_batch` transactions with.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// The origin which may anchor batches, resolving to the account of the submitter. It
+		/// should only admit the accounts of the keys of type `KEY_TYPE`.
+		type AnchorOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+		/// The number of blocks between two runs of the offchain worker. Zero disables it.
+		#[pallet::constant]
+		type AnchorInterval: Get<Self::BlockNumber>;
+
+		/// The names under which claim owners are known, attributed to their claims.
+		type IdentityProvider: IdentityProvider<Self::AccountId>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub (super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
 	#[pallet::getter(fn proofs)]
-	pub type Proofs<T: Config> =
-		StorageMap<_, Blake2_128Concat, Vec<u8>, (T::AccountId, T::BlockNumber)>;
+	pub type Proofs<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, ClaimInfoOf<T>>;
+
+	/// The proofs owned by each account.
+	#[pallet::storage]
+	pub type ClaimsOwned<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, Vec<u8>, ()>;
+
+	/// Co-signed claims which did not reach their threshold yet.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_claims)]
+	pub type PendingClaims<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, PendingClaim<T>>;
+
+	/// The accounts which approved a finalized co-signed claim.
+	#[pallet::storage]
+	#[pallet::getter(fn co_signers)]
+	pub type CoSigners<T: Config> =
+		StorageMap<_, Blake2_128Concat, Vec<u8>, BoundedVec<T::AccountId, T::MaxCoSigners>>;
+
+	/// The nonce to be signed by an account for its next `attest_on_behalf`.
+	#[pallet::storage]
+	#[pallet::getter(fn attest_nonce)]
+	pub type AttestNonces<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+	/// The parent and version of claims created through `create_revision`.
+	#[pallet::storage]
+	#[pallet::getter(fn revisions)]
+	pub type Revisions<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, Revision>;
+
+	/// The next version of a revised claim.
+	#[pallet::storage]
+	#[pallet::getter(fn successors)]
+	pub type Successors<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, Vec<u8>>;
+
+	/// The recipients offered a claim by its owner, awaiting their acceptance.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_transfers)]
+	pub type PendingTransfers<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, T::AccountId>;
+
+	/// The open disputes, by disputed claim.
+	#[pallet::storage]
+	#[pallet::getter(fn disputes)]
+	pub type Disputes<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, Dispute<T>>;
+
+	/// The Merkle roots of the batches anchored by offchain workers.
+	#[pallet::storage]
+	#[pallet::getter(fn anchors)]
+	pub type Anchors<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, AnchorInfoOf<T>>;
+
+	/// The claims to prune at the start of a given block.
+	#[pallet::storage]
+	#[pallet::getter(fn expiring)]
+	pub type Expiring<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<Vec<u8>, T::MaxExpiringPerBlock>,
+		ValueQuery,
+	>;
+
+	/// The pending claims to drop at the start of a given block, apart from `Expiring` so that
+	/// claims expiring in the same block cannot crowd them out.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_deadlines)]
+	pub type PendingDeadlines<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<Vec<u8>, T::MaxExpiringPerBlock>,
+		ValueQuery,
+	>;
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub (super) fn deposit_event)]
@@ -37,6 +327,39 @@ pub mod pallet {
 		ClaimCreated(T::AccountId, Vec<u8>),
 		ClaimRevoked(T::AccountId, Vec<u8>),
 		ClaimTransferred(T::AccountId, T::AccountId, Vec<u8>),
+		/// The expiry of a claim was changed. \[owner, claim, expires_at\]
+		ClaimRenewed(T::AccountId, Vec<u8>, Option<T::BlockNumber>),
+		/// A claim reached its expiry block and was pruned. \[owner, claim\]
+		ClaimExpired(T::AccountId, Vec<u8>),
+		/// A claim awaiting co-signer approval was created. \[creator, claim, threshold\]
+		CoSignedClaimCreated(T::AccountId, Vec<u8>, u32),
+		/// A co-signer approved a pending claim. \[signer, claim\]
+		ClaimApproved(T::AccountId, Vec<u8>),
+		/// A pending claim reached its threshold and became a proof. \[creator, claim\]
+		ClaimFinalized(T::AccountId, Vec<u8>),
+		/// A pending claim was not approved in time and was dropped. \[creator, claim\]
+		PendingClaimExpired(T::AccountId, Vec<u8>),
+		/// A relayer created a claim on behalf of its signer. \[relayer, owner, claim\]
+		ClaimAttested(T::AccountId, T::AccountId, Vec<u8>),
+		/// A claim was disputed. \[disputer, claim, evidence\]
+		ClaimDisputed(T::AccountId, Vec<u8>, T::Hash),
+		/// The owner of a disputed claim matched the bond. \[owner, claim, evidence\]
+		ClaimDefended(T::AccountId, Vec<u8>, T::Hash),
+		/// A dispute was resolved in favour of the owner. \[owner, claim, disputer\]
+		ClaimUpheld(T::AccountId, Vec<u8>, T::AccountId),
+		/// A dispute was resolved against the owner. \[owner, claim, disputer\]
+		ClaimStruck(T::AccountId, Vec<u8>, T::AccountId),
+		/// An owner offered a claim to a recipient. \[owner, recipient, claim\]
+		TransferOffered(T::AccountId, T::AccountId, Vec<u8>),
+		/// An owner withdrew the offer of a claim. \[owner, recipient, claim\]
+		TransferCancelled(T::AccountId, T::AccountId, Vec<u8>),
+		/// A new version of a claim was created. \[owner, parent, claim, version\]
+		ClaimRevised(T::AccountId, Vec<u8>, Vec<u8>, u32),
+		/// A batch of external hashes was anchored. \[submitter, root, leaves\]
+		BatchAnchored(T::AccountId, T::Hash, u32),
+		/// A claim was attributed to the judged identity of its new owner.
+		/// \[owner, claim, display_name\]
+		ClaimAttributed(T::AccountId, Vec<u8>, Vec<u8>),
 	}
 
 	#[pallet::error]
@@ -45,46 +368,648 @@ pub mod pallet {
 		ProofAlreadyExist,
 		ProofNotExist,
 		ProofTooLong,
+		/// The given expiry block is not in the future.
+		ExpiryInPast,
+		/// Too many claims already expire in the given block.
+		TooManyExpiring,
+		/// Too many pending claims already reach their deadline in the same block.
+		TooManyPending,
+		/// More co-signers were named than `MaxCoSigners` allows.
+		TooManyCoSigners,
+		/// The same co-signer was named twice.
+		DuplicateCoSigner,
+		/// The threshold is zero or greater than the number of co-signers.
+		InvalidThreshold,
+		/// There is no pending claim for the given claim.
+		PendingClaimNotExist,
+		/// The caller is not a co-signer of the pending claim.
+		NotCoSigner,
+		/// The caller already approved the pending claim.
+		AlreadyApproved,
+		/// The signature does not match the signer, claim and current nonce.
+		BadSignature,
+		/// The claim has an open dispute.
+		ClaimDisputed,
+		/// The claim has no open dispute.
+		DisputeNotExist,
+		/// Owners cannot dispute their own claims.
+		DisputeOwnClaim,
+		/// The dispute was already defended.
+		AlreadyDefended,
+		/// The caller cannot afford the dispute bond.
+		InsufficientBond,
+		/// Claims cannot be transferred to their owner.
+		TransferToSelf,
+		/// The claim is not being transferred.
+		TransferNotExist,
+		/// The caller is not the recipient of the transfer.
+		NotTransferRecipient,
+		/// The parent claim already has a newer version.
+		AlreadyRevised,
+		/// The document already has `MaxRevisions` revisions.
+		TooManyRevisions,
+		/// The Merkle root was anchored already.
+		AnchorAlreadyExist,
+		/// An anchored batch has no hashes.
+		EmptyBatch,
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::v1::migrate::<T>()
+		}
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let expiring = <Expiring<T>>::take(now);
+			let pending = <PendingDeadlines<T>>::take(now);
+			let weight = T::WeightInfo::on_initialize(expiring.len() as u32, pending.len() as u32);
+
+			for claim in expiring.into_iter() {
+				// A disputed claim outlives its expiry until the dispute is resolved.
+				if <Disputes<T>>::contains_key(&claim) {
+					continue
+				}
+				match <Proofs<T>>::get(&claim) {
+					Some(info) if info.expires_at == Some(now) => {
+						Self::remove_claim(&claim, &info.owner);
+						Self::deposit_event(Event::ClaimExpired(info.owner, claim));
+					},
+					_ => {},
+				}
+			}
+			for claim in pending.into_iter() {
+				match <PendingClaims<T>>::get(&claim) {
+					Some(pending) if pending.deadline == now => {
+						<PendingClaims<T>>::remove(&claim);
+						Self::deposit_event(Event::PendingClaimExpired(pending.creator, claim));
+					},
+					_ => {},
+				}
+			}
+
+			weight
+		}
+
+		fn offchain_worker(now: T::BlockNumber) {
+			let interval = T::AnchorInterval::get();
+			if interval.is_zero() || !(now % interval).is_zero() {
+				return
+			}
+
+			if let Err(e) = Self::anchor_next_batch() {
+				log::warn!(target: "runtime::poe", "Failed to anchor batch: {}", e);
+			}
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn create_claim(origin: OriginFor<T>, claim: Vec<u8>) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::create_claim(claim.len() as u32))]
+		pub fn create_claim(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(claim.len() <= T::MaxSize::get() as usize, <Error<T>>::ProofTooLong);
-			ensure!(!(<Proofs<T>>::contains_key(&claim)), <Error<T>>::ProofAlreadyExist);
-			<Proofs<T>>::insert(&claim, (who.clone(), <frame_system::Pallet<T>>::block_number()));
-			Self::deposit_event(Event::ClaimCreated(who, claim));
-			Ok(())
+			Self::do_create_claim(who, claim, expires_at)
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(T::WeightInfo::revoke_claim(claim.len() as u32))]
 		pub fn revoke_claim(origin: OriginFor<T>, claim: Vec<u8>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let (owner, _) = <Proofs<T>>::get(&claim).ok_or_else(|| <Error<T>>::ProofNotExist)?;
-			ensure!(owner == who, <Error<T>>::NotClaimOwner);
-			<Proofs<T>>::remove(&claim);
-			Self::deposit_event(Event::ClaimCreated(who, claim));
+			let info = <Proofs<T>>::get(&claim).ok_or_else(|| <Error<T>>::ProofNotExist)?;
+			ensure!(info.owner == who, <Error<T>>::NotClaimOwner);
+			ensure!(!<Disputes<T>>::contains_key(&claim), <Error<T>>::ClaimDisputed);
+
+			if let Some(at) = info.expires_at {
+				Self::cancel_expiry(&claim, at);
+			}
+			Self::remove_claim(&claim, &who);
+			Self::deposit_event(Event::ClaimRevoked(who, claim));
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		/// Offer an owned claim to `to`. The claim only changes hands once `to` calls
+		/// `accept_claim`, and the offer replaces any previous one.
+		#[pallet::weight(T::WeightInfo::transfer_claim(claim.len() as u32))]
 		pub fn transfer_claim(
 			origin: OriginFor<T>,
 			to: T::AccountId,
 			claim: Vec<u8>,
 		) -> DispatchResult {
 			let from = ensure_signed(origin)?;
-			let (owner, block_number) =
-				<Proofs<T>>::get(&claim).ok_or_else(|| <Error<T>>::ProofNotExist)?;
-			ensure!(owner == from, <Error<T>>::NotClaimOwner);
-			<Proofs<T>>::insert(&claim, (to.clone(), block_number));
-			Self::deposit_event(Event::ClaimTransferred(from, to, claim));
+			let info = <Proofs<T>>::get(&claim).ok_or_else(|| <Error<T>>::ProofNotExist)?;
+			ensure!(info.owner == from, <Error<T>>::NotClaimOwner);
+			ensure!(from != to, <Error<T>>::TransferToSelf);
+			ensure!(!<Disputes<T>>::contains_key(&claim), <Error<T>>::ClaimDisputed);
+
+			<PendingTransfers<T>>::insert(&claim, to.clone());
+			Self::deposit_event(Event::TransferOffered(from, to, claim));
 			Ok(())
 		}
+
+		/// Accept a claim offered to the caller, becoming its owner.
+		#[pallet::weight(T::WeightInfo::accept_claim(claim.len() as u32))]
+		pub fn accept_claim(origin: OriginFor<T>, claim: Vec<u8>) -> DispatchResult {
+			let to = ensure_signed(origin)?;
+			let recipient =
+				<PendingTransfers<T>>::get(&claim).ok_or_else(|| <Error<T>>::TransferNotExist)?;
+			ensure!(recipient == to, <Error<T>>::NotTransferRecipient);
+			ensure!(!<Disputes<T>>::contains_key(&claim), <Error<T>>::ClaimDisputed);
+			let mut info = <Proofs<T>>::get(&claim).ok_or_else(|| <Error<T>>::ProofNotExist)?;
+			let from = info.owner.clone();
+
+			<ClaimsOwned<T>>::remove(&from, &claim);
+			<ClaimsOwned<T>>::insert(&to, &claim, ());
+			<PendingTransfers<T>>::remove(&claim);
+			info.owner = to.clone();
+			<Proofs<T>>::insert(&claim, info);
+			Self::deposit_event(Event::ClaimTransferred(from, to.clone(), claim.clone()));
+			Self::attribute_claim(&to, claim);
+			Ok(())
+		}
+
+		/// Withdraw the pending offer of an owned claim.
+		#[pallet::weight(T::WeightInfo::cancel_transfer(claim.len() as u32))]
+		pub fn cancel_transfer(origin: OriginFor<T>, claim: Vec<u8>) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			let info = <Proofs<T>>::get(&claim).ok_or_else(|| <Error<T>>::ProofNotExist)?;
+			ensure!(info.owner == from, <Error<T>>::NotClaimOwner);
+			let to =
+				<PendingTransfers<T>>::take(&claim).ok_or_else(|| <Error<T>>::TransferNotExist)?;
+
+			Self::deposit_event(Event::TransferCancelled(from, to, claim));
+			Ok(())
+		}
+
+		/// Create `claim` as the next version of the owned `parent` claim.
+		#[pallet::weight(T::WeightInfo::create_revision(claim.len() as u32))]
+		pub fn create_revision(
+			origin: OriginFor<T>,
+			parent: Vec<u8>,
+			claim: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let info = <Proofs<T>>::get(&parent).ok_or_else(|| <Error<T>>::ProofNotExist)?;
+			ensure!(info.owner == who, <Error<T>>::NotClaimOwner);
+			ensure!(!<Successors<T>>::contains_key(&parent), <Error<T>>::AlreadyRevised);
+			let version = Self::revisions(&parent).map_or(1, |revision| revision.version + 1);
+			ensure!(version <= T::MaxRevisions::get(), <Error<T>>::TooManyRevisions);
+
+			Self::do_create_claim(who.clone(), claim.clone(), None)?;
+			<Revisions<T>>::insert(&claim, Revision { parent: Some(parent.clone()), version });
+			<Successors<T>>::insert(&parent, claim.clone());
+			Self::deposit_event(Event::ClaimRevised(who, parent, claim, version));
+			Ok(())
+		}
+
+		/// Move the expiry of an owned claim to `expires_at`, or make it permanent with `None`.
+		#[pallet::weight(T::WeightInfo::renew_claim(claim.len() as u32))]
+		pub fn renew_claim(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut info = <Proofs<T>>::get(&claim).ok_or_else(|| <Error<T>>::ProofNotExist)?;
+			ensure!(info.owner == who, <Error<T>>::NotClaimOwner);
+			ensure!(!<Disputes<T>>::contains_key(&claim), <Error<T>>::ClaimDisputed);
+			Self::ensure_future(expires_at)?;
+
+			if let Some(at) = expires_at {
+				Self::schedule_expiry(&claim, at)?;
+			}
+			if let Some(at) = info.expires_at {
+				Self::cancel_expiry(&claim, at);
+			}
+			info.expires_at = expires_at;
+			<Proofs<T>>::insert(&claim, info);
+			Self::deposit_event(Event::ClaimRenewed(who, claim, expires_at));
+			Ok(())
+		}
+
+		/// Create a claim which is owned by the caller once `threshold` of `signers` approved
+		/// it through `approve_claim` within `PendingClaimWindow` blocks.
+		#[pallet::weight(
+			T::WeightInfo::create_cosigned_claim(claim.len() as u32, signers.len() as u32)
+		)]
+		pub fn create_cosigned_claim(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			signers: Vec<T::AccountId>,
+			threshold: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(claim.len() <= T::MaxSize::get() as usize, <Error<T>>::ProofTooLong);
+			ensure!(!Self::is_claimed(&claim), <Error<T>>::ProofAlreadyExist);
+			ensure!(
+				threshold > 0 && threshold as usize <= signers.len(),
+				<Error<T>>::InvalidThreshold
+			);
+			// Bound the signers before looking for duplicates among them.
+			let signers: BoundedVec<_, T::MaxCoSigners> =
+				signers.try_into().map_err(|_| <Error<T>>::TooManyCoSigners)?;
+			let mut sorted = signers.to_vec();
+			sorted.sort();
+			ensure!(
+				sorted.windows(2).all(|pair| pair[0] != pair[1]),
+				<Error<T>>::DuplicateCoSigner
+			);
+
+			let deadline = <frame_system::Pallet<T>>::block_number() + T::PendingClaimWindow::get();
+			<PendingDeadlines<T>>::try_mutate(deadline, |claims| claims.try_push(claim.clone()))
+				.map_err(|_| <Error<T>>::TooManyPending)?;
+			let pending = PendingClaim {
+				creator: who.clone(),
+				signers,
+				threshold,
+				approvals: Default::default(),
+				deadline,
+			};
+			<PendingClaims<T>>::insert(&claim, pending);
+			Self::deposit_event(Event::CoSignedClaimCreated(who, claim, threshold));
+			Ok(())
+		}
+
+		/// Approve a pending co-signed claim, finalizing it if this meets its threshold.
+		#[pallet::weight(T::WeightInfo::approve_claim(claim.len() as u32))]
+		pub fn approve_claim(origin: OriginFor<T>, claim: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut pending =
+				<PendingClaims<T>>::get(&claim).ok_or_else(|| <Error<T>>::PendingClaimNotExist)?;
+			ensure!(pending.signers.contains(&who), <Error<T>>::NotCoSigner);
+			ensure!(!pending.approvals.contains(&who), <Error<T>>::AlreadyApproved);
+
+			// Cannot fail, every signer approves at most once.
+			pending
+				.approvals
+				.try_push(who.clone())
+				.map_err(|_| <Error<T>>::TooManyCoSigners)?;
+
+			if (pending.approvals.len() as u32) < pending.threshold {
+				<PendingClaims<T>>::insert(&claim, pending);
+				Self::deposit_event(Event::ClaimApproved(who, claim));
+				return Ok(())
+			}
+
+			<ClaimsOwned<T>>::insert(&pending.creator, &claim, ());
+			Self::deposit_event(Event::ClaimApproved(who, claim.clone()));
+			<PendingDeadlines<T>>::mutate(pending.deadline, |claims| {
+				if let Some(index) = claims.iter().position(|c| c == &claim) {
+					claims.swap_remove(index);
+				}
+			});
+			<PendingClaims<T>>::remove(&claim);
+			let info = ClaimInfo {
+				owner: pending.creator.clone(),
+				created_at: <frame_system::Pallet<T>>::block_number(),
+				expires_at: None,
+			};
+			<Proofs<T>>::insert(&claim, info);
+			<CoSigners<T>>::insert(&claim, pending.approvals);
+			Self::deposit_event(Event::ClaimFinalized(pending.creator.clone(), claim.clone()));
+			Self::attribute_claim(&pending.creator, claim);
+			Ok(())
+		}
+
+		/// Create a claim owned by `signer`, submitted by a relayer paying the fees.
+		///
+		/// `signature` must be made by `signer` over `attest_payload(claim, nonce)`, where
+		/// `nonce` is the signer's current `attest_nonce`.
+		#[pallet::weight(T::WeightInfo::attest_on_behalf(claim.len() as u32))]
+		pub fn attest_on_behalf(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			signer: T::AccountId,
+			signature: T::OffchainSignature,
+		) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			let nonce = Self::attest_nonce(&signer);
+			let payload = Self::attest_payload(&claim, nonce);
+			ensure!(signature.verify(&payload[..], &signer), <Error<T>>::BadSignature);
+
+			Self::do_create_claim(signer.clone(), claim.clone(), None)?;
+			<AttestNonces<T>>::insert(&signer, nonce.wrapping_add(1));
+			Self::deposit_event(Event::ClaimAttested(relayer, signer, claim));
+			Ok(())
+		}
+
+		/// Contest the authorship of `claim`, reserving `DisputeBond` from the caller.
+		#[pallet::weight(T::WeightInfo::dispute_claim(claim.len() as u32))]
+		pub fn dispute_claim(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			evidence: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let info = <Proofs<T>>::get(&claim).ok_or_else(|| <Error<T>>::ProofNotExist)?;
+			ensure!(info.owner != who, <Error<T>>::DisputeOwnClaim);
+			ensure!(!<Disputes<T>>::contains_key(&claim), <Error<T>>::ClaimDisputed);
+
+			let bond = T::DisputeBond::get();
+			T::Currency::reserve(&who, bond).map_err(|_| <Error<T>>::InsufficientBond)?;
+			let dispute = Dispute { disputer: who.clone(), evidence, bond, defender: None };
+			<Disputes<T>>::insert(&claim, dispute);
+			Self::deposit_event(Event::ClaimDisputed(who, claim, evidence));
+			Ok(())
+		}
+
+		/// Defend a disputed claim owned by the caller, matching the disputer's bond.
+		#[pallet::weight(T::WeightInfo::defend_claim(claim.len() as u32))]
+		pub fn defend_claim(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			evidence: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let info = <Proofs<T>>::get(&claim).ok_or_else(|| <Error<T>>::ProofNotExist)?;
+			ensure!(info.owner == who, <Error<T>>::NotClaimOwner);
+			let mut dispute =
+				<Disputes<T>>::get(&claim).ok_or_else(|| <Error<T>>::DisputeNotExist)?;
+			ensure!(dispute.defender.is_none(), <Error<T>>::AlreadyDefended);
+
+			T::Currency::reserve(&who, dispute.bond).map_err(|_| <Error<T>>::InsufficientBond)?;
+			dispute.defender = Some(who.clone());
+			<Disputes<T>>::insert(&claim, dispute);
+			Self::deposit_event(Event::ClaimDefended(who, claim, evidence));
+			Ok(())
+		}
+
+		/// Close the dispute of `claim`. If `uphold`, the claim stays and the disputer's bond is
+		/// slashed; otherwise the claim is removed and the defender's bond, if any, is slashed.
+		/// An upheld claim which expired during the dispute is removed as well.
+		#[pallet::weight(T::WeightInfo::resolve_dispute(claim.len() as u32))]
+		pub fn resolve_dispute(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			uphold: bool,
+		) -> DispatchResult {
+			T::ResolveOrigin::ensure_origin(origin)?;
+			let info = <Proofs<T>>::get(&claim).ok_or_else(|| <Error<T>>::ProofNotExist)?;
+			let dispute = <Disputes<T>>::take(&claim).ok_or_else(|| <Error<T>>::DisputeNotExist)?;
+
+			if uphold {
+				Self::slash_bond(&dispute.disputer, dispute.bond);
+				if let Some(defender) = &dispute.defender {
+					T::Currency::unreserve(defender, dispute.bond);
+				}
+				Self::deposit_event(Event::ClaimUpheld(
+					info.owner.clone(),
+					claim.clone(),
+					dispute.disputer,
+				));
+				let now = <frame_system::Pallet<T>>::block_number();
+				if info.expires_at.map_or(false, |at| at <= now) {
+					Self::remove_claim(&claim, &info.owner);
+					Self::deposit_event(Event::ClaimExpired(info.owner, claim));
+				}
+			} else {
+				T::Currency::unreserve(&dispute.disputer, dispute.bond);
+				if let Some(defender) = &dispute.defender {
+					Self::slash_bond(defender, dispute.bond);
+				}
+				if let Some(at) = info.expires_at {
+					Self::cancel_expiry(&claim, at);
+				}
+				Self::remove_claim(&claim, &info.owner);
+				Self::deposit_event(Event::ClaimStruck(info.owner, claim, dispute.disputer));
+			}
+			Ok(())
+		}
+
+		/// Anchor the Merkle `root` of a batch of `leaves` external hashes. Submitted by the
+		/// offchain worker through `AnchorOrigin`.
+		#[pallet::weight(T::WeightInfo::anchor_batch())]
+		pub fn anchor_batch(origin: OriginFor<T>, root: T::Hash, leaves: u32) -> DispatchResult {
+			let who = T::AnchorOrigin::ensure_origin(origin)?;
+			ensure!(leaves > 0, <Error<T>>::EmptyBatch);
+			ensure!(!<Anchors<T>>::contains_key(&root), <Error<T>>::AnchorAlreadyExist);
+
+			let anchored_at = <frame_system::Pallet<T>>::block_number();
+			<Anchors<T>>::insert(&root, AnchorInfo { submitter: who.clone(), anchored_at, leaves });
+			Self::deposit_event(Event::BatchAnchored(who, root, leaves));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The root of the binary Merkle tree over `leaves`. A node without a sibling is carried
+		/// up to the next level unchanged.
+		pub fn merkle_root(leaves: &[T::Hash]) -> T::Hash {
+			let mut level = leaves.to_vec();
+			while level.len() > 1 {
+				level = level
+					.chunks(2)
+					.map(|pair| match pair {
+						[left, right] => T::Hashing::hash_of(&(left, right)),
+						_ => pair[0],
+					})
+					.collect();
+			}
+			level.pop().unwrap_or_default()
+		}
+
+		/// Fetch the next batch from `ANCHOR_ENDPOINT` and submit its root through a signed
+		/// `anchor_batch`. Does nothing on nodes without a key of type `KEY_TYPE`.
+		fn anchor_next_batch() -> Result<(), &'static str> {
+			let signer = Signer::<T, T::AuthorityId>::any_account();
+			if !signer.can_sign() {
+				return Ok(())
+			}
+
+			let leaves = Self::fetch_batch().map_err(|_| "fetching the batch failed")?;
+			if leaves.is_empty() {
+				return Ok(())
+			}
+			let root = Self::merkle_root(&leaves);
+			if <Anchors<T>>::contains_key(&root) {
+				return Ok(())
+			}
+
+			let count = leaves.len() as u32;
+			match signer.send_signed_transaction(|_| Call::anchor_batch { root, leaves: count }) {
+				Some((_, Ok(()))) => Ok(()),
+				Some((_, Err(()))) => Err("submitting the transaction failed"),
+				None => Err("no account to sign the transaction with"),
+			}
+		}
+
+		/// The hashes served by `ANCHOR_ENDPOINT`.
+		fn fetch_batch() -> Result<Vec<T::Hash>, http::Error> {
+			let deadline =
+				sp_io::offchain::timestamp().add(Duration::from_millis(ANCHOR_TIMEOUT_MS));
+			let pending = http::Request::get(ANCHOR_ENDPOINT)
+				.deadline(deadline)
+				.send()
+				.map_err(|_| http::Error::IoError)?;
+			let response =
+				pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+			if response.code != 200 {
+				return Err(http::Error::Unknown)
+			}
+
+			let body = response.body().collect::<Vec<u8>>();
+			let mut input = &body[..];
+			let mut leaves = Vec::new();
+			while !input.is_empty() {
+				leaves.push(T::Hash::decode(&mut input).map_err(|_| http::Error::Unknown)?);
+			}
+			Ok(leaves)
+		}
+
+		/// The message a signer signs to let a relayer attest `claim` for them. It is bound to
+		/// this chain through the genesis hash and to a single use through `nonce`.
+		pub fn attest_payload(claim: &[u8], nonce: u64) -> Vec<u8> {
+			let genesis_hash = <frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero());
+			(&b"poe:attest"[..], claim, nonce, genesis_hash).encode()
+		}
+
+		/// Up to `CLAIMS_PER_PAGE` proofs owned by `owner`, following the proof `after` or from
+		/// the first one if `None`. Pass the last proof of a page to get the next one.
+		pub fn claims_page(owner: &T::AccountId, after: Option<Vec<u8>>) -> Vec<Vec<u8>> {
+			let claims = match after {
+				Some(after) => <ClaimsOwned<T>>::iter_prefix_from(
+					owner,
+					<ClaimsOwned<T>>::hashed_key_for(owner, after),
+				),
+				None => <ClaimsOwned<T>>::iter_prefix(owner),
+			};
+			claims.map(|(claim, ())| claim).take(CLAIMS_PER_PAGE as usize).collect()
+		}
+
+		/// Every version of the document `claim` belongs to, from the original claim to the
+		/// latest revision.
+		pub fn revision_history(claim: &Vec<u8>) -> Vec<Vec<u8>> {
+			let max = T::MaxRevisions::get();
+			let mut history = vec![claim.clone()];
+
+			let mut current = claim.clone();
+			while let Some(parent) = Self::revisions(&current).and_then(|revision| revision.parent)
+			{
+				if history.len() as u32 > max {
+					break
+				}
+				history.push(parent.clone());
+				current = parent;
+			}
+			history.reverse();
+
+			let mut current = claim.clone();
+			while let Some(next) = Self::successors(&current) {
+				if history.len() as u32 > max {
+					break
+				}
+				history.push(next.clone());
+				current = next;
+			}
+			history
+		}
+
+		/// The proof for `claim`, if any, along with the display name of its owner.
+		pub fn claim_details(claim: &Vec<u8>) -> Option<ClaimDetailsOf<T>> {
+			Self::proofs(claim).map(|info| ClaimDetails {
+				owner_display_name: T::IdentityProvider::display_name(&info.owner),
+				info,
+			})
+		}
+
+		/// The proof for `claim` if it exists and has not expired yet.
+		pub fn verify_claim(claim: &Vec<u8>) -> Option<ClaimInfoOf<T>> {
+			let now = <frame_system::Pallet<T>>::block_number();
+			Self::proofs(claim).filter(|info| info.expires_at.map_or(true, |at| at > now))
+		}
+
+		fn do_create_claim(
+			owner: T::AccountId,
+			claim: Vec<u8>,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			ensure!(claim.len() <= T::MaxSize::get() as usize, <Error<T>>::ProofTooLong);
+			ensure!(!Self::is_claimed(&claim), <Error<T>>::ProofAlreadyExist);
+			Self::ensure_future(expires_at)?;
+
+			if let Some(at) = expires_at {
+				Self::schedule_expiry(&claim, at)?;
+			}
+			<ClaimsOwned<T>>::insert(&owner, &claim, ());
+			let info = ClaimInfo {
+				owner: owner.clone(),
+				created_at: <frame_system::Pallet<T>>::block_number(),
+				expires_at,
+			};
+			<Proofs<T>>::insert(&claim, info);
+			Self::deposit_event(Event::ClaimCreated(owner.clone(), claim.clone()));
+			Self::attribute_claim(&owner, claim);
+			Ok(())
+		}
+
+		/// Remove the proof of `claim` and everything recorded alongside it, except its expiry.
+		/// The bonds of a dispute still open are returned.
+		fn remove_claim(claim: &Vec<u8>, owner: &T::AccountId) {
+			<Proofs<T>>::remove(claim);
+			<CoSigners<T>>::remove(claim);
+			<PendingTransfers<T>>::remove(claim);
+			<ClaimsOwned<T>>::remove(owner, claim);
+
+			// Cut the history at the removed claim, letting the parent be revised again.
+			if let Some(successor) = <Successors<T>>::take(claim) {
+				<Revisions<T>>::mutate(&successor, |revision| {
+					if let Some(revision) = revision {
+						revision.parent = None;
+					}
+				});
+			}
+			if let Some(parent) = <Revisions<T>>::take(claim).and_then(|revision| revision.parent) {
+				if Self::successors(&parent).as_ref() == Some(claim) {
+					<Successors<T>>::remove(&parent);
+				}
+			}
+
+			if let Some(dispute) = <Disputes<T>>::take(claim) {
+				T::Currency::unreserve(&dispute.disputer, dispute.bond);
+				if let Some(defender) = &dispute.defender {
+					T::Currency::unreserve(defender, dispute.bond);
+				}
+			}
+		}
+
+		/// Note the display name of `owner` on `claim`, if its identity was judged.
+		fn attribute_claim(owner: &T::AccountId, claim: Vec<u8>) {
+			if let Some(name) = T::IdentityProvider::display_name(owner) {
+				Self::deposit_event(Event::ClaimAttributed(owner.clone(), claim, name));
+			}
+		}
+
+		fn slash_bond(who: &T::AccountId, bond: BalanceOf<T>) {
+			let (imbalance, _) = T::Currency::slash_reserved(who, bond);
+			T::Slashed::on_unbalanced(imbalance);
+		}
+
+		/// Whether `claim` is a proof or a pending co-signed claim.
+		fn is_claimed(claim: &Vec<u8>) -> bool {
+			<Proofs<T>>::contains_key(claim) || <PendingClaims<T>>::contains_key(claim)
+		}
+
+		fn ensure_future(expires_at: Option<T::BlockNumber>) -> Result<(), Error<T>> {
+			match expires_at {
+				Some(at) if at <= <frame_system::Pallet<T>>::block_number() =>
+					Err(<Error<T>>::ExpiryInPast),
+				_ => Ok(()),
+			}
+		}
+
+		fn schedule_expiry(claim: &Vec<u8>, at: T::BlockNumber) -> Result<(), Error<T>> {
+			<Expiring<T>>::try_mutate(at, |claims| claims.try_push(claim.clone()))
+				.map_err(|_| <Error<T>>::TooManyExpiring)
+		}
+
+		fn cancel_expiry(claim: &Vec<u8>, at: T::BlockNumber) {
+			<Expiring<T>>::mutate(at, |claims| {
+				if let Some(index) = claims.iter().position(|c| c == claim) {
+					claims.swap_remove(index);
+				}
+			});
+		}
 	}
 }
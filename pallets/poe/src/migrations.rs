@@ -0,0 +1,33 @@
+//! Storage migrations for pallet-poe.
+
+use super::*;
+use frame_support::{
+	traits::{Get, GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+
+/// Moves the proofs stored before claims could expire to `ClaimInfo`, and indexes them by owner.
+pub mod v1 {
+	use super::*;
+
+	/// The proofs stored as `(owner, created_at)`, before version 1.
+	type OldProof<T> =
+		(<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber);
+
+	pub fn migrate<T: Config>() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut count: Weight = 0;
+		Proofs::<T>::translate::<OldProof<T>, _>(|claim, (owner, created_at)| {
+			count += 1;
+			ClaimsOwned::<T>::insert(&owner, &claim, ());
+			Some(ClaimInfo { owner, created_at, expires_at: None })
+		});
+		StorageVersion::new(1).put::<Pallet<T>>();
+		log::info!(target: "runtime::poe", "Migrated {} proofs to v1", count);
+
+		T::DbWeight::get().reads_writes(count + 1, 2 * count + 1)
+	}
+}
@@ -1,11 +1,12 @@
 use crate as pallet_poe;
 
-use frame_support::{parameter_types, traits::ConstU32};
+use frame_support::{ord_parameter_types, parameter_types, traits::ConstU32};
 use frame_system as system;
+use frame_system::{EnsureRoot, EnsureSignedBy};
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -19,6 +20,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Poe: pallet_poe::{Pallet, Call, Storage, Event<T>},
 	}
 );
@@ -26,6 +28,14 @@ frame_support::construct_runtime!(
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 42;
+	pub const PendingClaimWindow: u64 = 5;
+	pub const ExistentialDeposit: u64 = 1;
+	pub const DisputeBond: u64 = 10;
+	pub const AnchorInterval: u64 = 2;
+}
+
+ord_parameter_types! {
+	pub const Anchorer: u64 = 1;
 }
 
 impl system::Config for Test {
@@ -46,7 +56,7 @@ impl system::Config for Test {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -54,13 +64,94 @@ impl system::Config for Test {
 	type OnSetCode = ();
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
 impl pallet_poe::Config for Test {
 	type Event = Event;
 
 	type MaxSize = ConstU32<8>;
+	type MaxExpiringPerBlock = ConstU32<2>;
+	type MaxRevisions = ConstU32<3>;
+	type MaxCoSigners = ConstU32<3>;
+	type PendingClaimWindow = PendingClaimWindow;
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	type Currency = Balances;
+	type DisputeBond = DisputeBond;
+	type ResolveOrigin = EnsureRoot<u64>;
+	type Slashed = ();
+	type AuthorityId = TestAuthId;
+	type AnchorOrigin = EnsureSignedBy<Anchorer, u64>;
+	type AnchorInterval = AnchorInterval;
+	type IdentityProvider = TestIdentity;
+	type WeightInfo = ();
+}
+
+/// Only account 7 has a judged identity, named "Alice".
+pub struct TestIdentity;
+
+impl pallet_poe::IdentityProvider<u64> for TestIdentity {
+	fn display_name(who: &u64) -> Option<Vec<u8>> {
+		(*who == 7).then(|| b"Alice".to_vec())
+	}
+}
+
+/// Signs with the keys set through `UintAuthorityId::set_all_keys`.
+pub struct TestAuthId;
+
+impl system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+	type GenericPublic = UintAuthorityId;
+}
+
+pub type Extrinsic = TestXt<Call, ()>;
+
+impl system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		_public: UintAuthorityId,
+		account: u64,
+		_nonce: u64,
+	) -> Option<(Call, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (account, ())))
+	}
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 100), (2, 100), (3, 5)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	// Events are not deposited in the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
@@ -1,16 +1,32 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{mock::*, ClaimInfo, Error, Proofs, ANCHOR_ENDPOINT, CLAIMS_PER_PAGE};
+use codec::Decode;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, Hooks, StorageVersion},
+};
+use sp_core::{
+	offchain::{testing, OffchainDbExt, OffchainWorkerExt, TransactionPoolExt},
+	H256,
+};
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	traits::{BlakeTwo256, Hash},
+	DispatchError,
+};
 
 #[test]
 fn normal_operation_should_works() {
 	new_test_ext().execute_with(|| {
 		let claim = b"12345678".to_vec();
 		// test create claim
-		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), None));
 		// test transfer claim
 		assert_ok!(Poe::transfer_claim(Origin::signed(1), 2, claim.clone()));
+		assert_ok!(Poe::accept_claim(Origin::signed(2), claim.clone()));
 		// test revoke claim
-		assert_ok!(Poe::revoke_claim(Origin::signed(2), claim));
+		assert_ok!(Poe::revoke_claim(Origin::signed(2), claim.clone()));
+		System::assert_last_event(Event::Poe(crate::Event::ClaimRevoked(2, claim.clone())));
+		assert_eq!(Poe::proofs(&claim), None);
 	});
 }
 
@@ -18,8 +34,11 @@ fn normal_operation_should_works() {
 fn should_return_exist_error_when_create_claim_and_given_an_exist_claim() {
 	new_test_ext().execute_with(|| {
 		let claim = b"12345678".to_vec();
-		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone()));
-		assert_noop!(Poe::create_claim(Origin::signed(1), claim), <Error<Test>>::ProofAlreadyExist);
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), None));
+		assert_noop!(
+			Poe::create_claim(Origin::signed(1), claim, None),
+			<Error<Test>>::ProofAlreadyExist
+		);
 	});
 }
 
@@ -27,7 +46,7 @@ fn should_return_exist_error_when_create_claim_and_given_an_exist_claim() {
 fn should_return_too_long_error_when_create_claim_and_given_an_long_claim() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			Poe::create_claim(Origin::signed(1), b"123456789".to_vec()),
+			Poe::create_claim(Origin::signed(1), b"123456789".to_vec(), None),
 			<Error<Test>>::ProofTooLong
 		);
 	});
@@ -37,7 +56,7 @@ fn should_return_too_long_error_when_create_claim_and_given_an_long_claim() {
 fn should_return_not_owner_error_when_not_owner_call_revoke_claim() {
 	new_test_ext().execute_with(|| {
 		let claim = b"12345678".to_vec();
-		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), None));
 		assert_noop!(Poe::revoke_claim(Origin::signed(2), claim), <Error<Test>>::NotClaimOwner);
 	});
 }
@@ -56,16 +75,758 @@ fn should_return_not_exist_error_when_revoke_claim_and_given_an_nonexit_claim()
 fn should_return_not_owner_error_when_not_owner_call_transfer_claim() {
 	new_test_ext().execute_with(|| {
 		let claim = b"12345678".to_vec();
-		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone()));
-		assert_noop!(Poe::transfer_claim(Origin::signed(2), 1, claim), <Error<Test>>::NotClaimOwner);
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), None));
+		assert_noop!(
+			Poe::transfer_claim(Origin::signed(2), 1, claim),
+			<Error<Test>>::NotClaimOwner
+		);
 	});
 }
 
 #[test]
 fn should_return_not_exist_error_when_transfer_claim_and_given_an_nonexit_claim() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Poe::create_claim(Origin::signed(1), b"12345678".to_vec()));
-		assert_noop!(Poe::transfer_claim(Origin::signed(1), 2, b"87654321".to_vec()), <Error<Test>>::ProofNotExist);
+		assert_ok!(Poe::create_claim(Origin::signed(1), b"12345678".to_vec(), None));
+		assert_noop!(
+			Poe::transfer_claim(Origin::signed(1), 2, b"87654321".to_vec()),
+			<Error<Test>>::ProofNotExist
+		);
+	});
+}
+
+#[test]
+fn expired_claim_should_be_pruned() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), Some(3)));
+		assert_eq!(Poe::expiring(3).into_inner(), vec![claim.clone()]);
+
+		Poe::on_initialize(2);
+		assert!(Poe::proofs(&claim).is_some());
+
+		System::set_block_number(3);
+		Poe::on_initialize(3);
+		assert_eq!(Poe::proofs(&claim), None);
+		assert!(Poe::expiring(3).is_empty());
+		System::assert_last_event(Event::Poe(crate::Event::ClaimExpired(1, claim)));
+	});
+}
+
+#[test]
+fn should_return_expiry_in_past_error_when_create_claim() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Poe::create_claim(Origin::signed(1), b"12345678".to_vec(), Some(1)),
+			<Error<Test>>::ExpiryInPast
+		);
+	});
+}
+
+#[test]
+fn should_return_too_many_expiring_error_when_create_claim() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Poe::create_claim(Origin::signed(1), b"1".to_vec(), Some(5)));
+		assert_ok!(Poe::create_claim(Origin::signed(1), b"2".to_vec(), Some(5)));
+		assert_noop!(
+			Poe::create_claim(Origin::signed(1), b"3".to_vec(), Some(5)),
+			<Error<Test>>::TooManyExpiring
+		);
+	});
+}
+
+#[test]
+fn renew_claim_should_move_expiry() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), Some(3)));
+		assert_ok!(Poe::renew_claim(Origin::signed(1), claim.clone(), Some(10)));
+		assert!(Poe::expiring(3).is_empty());
+		assert_eq!(Poe::expiring(10).into_inner(), vec![claim.clone()]);
+
+		System::set_block_number(3);
+		Poe::on_initialize(3);
+		assert_eq!(Poe::proofs(&claim).unwrap().expires_at, Some(10));
+
+		assert_ok!(Poe::renew_claim(Origin::signed(1), claim.clone(), None));
+		assert!(Poe::expiring(10).is_empty());
+		assert_eq!(Poe::proofs(&claim).unwrap().expires_at, None);
+	});
+}
+
+#[test]
+fn should_return_not_owner_error_when_not_owner_call_renew_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), None));
+		assert_noop!(
+			Poe::renew_claim(Origin::signed(2), claim, Some(5)),
+			<Error<Test>>::NotClaimOwner
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_should_cancel_expiry() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), Some(3)));
+		assert_ok!(Poe::revoke_claim(Origin::signed(1), claim));
+		assert!(Poe::expiring(3).is_empty());
+	});
+}
+
+#[test]
+fn cosigned_claim_should_be_finalized_when_threshold_met() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_ok!(Poe::create_cosigned_claim(Origin::signed(1), claim.clone(), vec![1, 2, 3], 2));
+		assert_noop!(
+			Poe::create_claim(Origin::signed(4), claim.clone(), None),
+			<Error<Test>>::ProofAlreadyExist
+		);
+
+		assert_ok!(Poe::approve_claim(Origin::signed(2), claim.clone()));
+		assert_eq!(Poe::proofs(&claim), None);
+
+		assert_ok!(Poe::approve_claim(Origin::signed(3), claim.clone()));
+		assert_eq!(Poe::proofs(&claim).unwrap().owner, 1);
+		assert_eq!(Poe::co_signers(&claim).unwrap().into_inner(), vec![2, 3]);
+		assert_eq!(Poe::pending_claims(&claim), None);
+		assert!(Poe::expiring(6).is_empty());
+		System::assert_last_event(Event::Poe(crate::Event::ClaimFinalized(1, claim)));
+	});
+}
+
+#[test]
+fn pending_claim_should_expire_after_window() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_ok!(Poe::create_cosigned_claim(Origin::signed(1), claim.clone(), vec![2, 3], 2));
+		assert_ok!(Poe::approve_claim(Origin::signed(2), claim.clone()));
+
+		System::set_block_number(6);
+		Poe::on_initialize(6);
+		assert_eq!(Poe::pending_claims(&claim), None);
+		assert_eq!(Poe::proofs(&claim), None);
+		System::assert_last_event(Event::Poe(crate::Event::PendingClaimExpired(1, claim.clone())));
+
+		assert_noop!(
+			Poe::approve_claim(Origin::signed(3), claim),
+			<Error<Test>>::PendingClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn expiring_claims_should_not_crowd_out_pending_claims() {
+	new_test_ext().execute_with(|| {
+		// Fill the expiry slot of the block the pending claims reach their deadline in.
+		assert_ok!(Poe::create_claim(Origin::signed(1), b"1".to_vec(), Some(6)));
+		assert_ok!(Poe::create_claim(Origin::signed(1), b"2".to_vec(), Some(6)));
+
+		assert_ok!(Poe::create_cosigned_claim(Origin::signed(1), b"3".to_vec(), vec![2, 3], 2));
+		assert_ok!(Poe::create_cosigned_claim(Origin::signed(1), b"4".to_vec(), vec![2, 3], 2));
+		assert_noop!(
+			Poe::create_cosigned_claim(Origin::signed(1), b"5".to_vec(), vec![2, 3], 2),
+			<Error<Test>>::TooManyPending
+		);
+
+		System::set_block_number(6);
+		Poe::on_initialize(6);
+		assert_eq!(Poe::proofs(&b"1".to_vec()), None);
+		assert_eq!(Poe::pending_claims(&b"3".to_vec()), None);
+		assert!(Poe::pending_deadlines(6).is_empty());
+	});
+}
+
+#[test]
+fn should_return_invalid_threshold_error_when_create_cosigned_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_noop!(
+			Poe::create_cosigned_claim(Origin::signed(1), claim.clone(), vec![2, 3], 0),
+			<Error<Test>>::InvalidThreshold
+		);
+		assert_noop!(
+			Poe::create_cosigned_claim(Origin::signed(1), claim, vec![2, 3], 3),
+			<Error<Test>>::InvalidThreshold
+		);
+	});
+}
+
+#[test]
+fn should_return_signer_errors_when_create_cosigned_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_noop!(
+			Poe::create_cosigned_claim(Origin::signed(1), claim.clone(), vec![2, 3, 2], 2),
+			<Error<Test>>::DuplicateCoSigner
+		);
+		assert_noop!(
+			Poe::create_cosigned_claim(Origin::signed(1), claim, vec![2, 3, 4, 5], 2),
+			<Error<Test>>::TooManyCoSigners
+		);
+	});
+}
+
+#[test]
+fn should_return_approval_errors_when_approve_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_ok!(Poe::create_cosigned_claim(Origin::signed(1), claim.clone(), vec![2, 3], 2));
+		assert_noop!(
+			Poe::approve_claim(Origin::signed(4), claim.clone()),
+			<Error<Test>>::NotCoSigner
+		);
+		assert_ok!(Poe::approve_claim(Origin::signed(2), claim.clone()));
+		assert_noop!(Poe::approve_claim(Origin::signed(2), claim), <Error<Test>>::AlreadyApproved);
+	});
+}
+
+#[test]
+fn attest_on_behalf_should_create_claim_owned_by_signer() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		let signature = TestSignature(1, Poe::attest_payload(&claim, 0));
+		assert_ok!(Poe::attest_on_behalf(Origin::signed(9), claim.clone(), 1, signature));
+		assert_eq!(Poe::proofs(&claim).unwrap().owner, 1);
+		assert_eq!(Poe::attest_nonce(1), 1);
+		System::assert_last_event(Event::Poe(crate::Event::ClaimAttested(9, 1, claim)));
+	});
+}
+
+#[test]
+fn should_return_bad_signature_error_when_attest_on_behalf_is_replayed() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		let signature = TestSignature(1, Poe::attest_payload(&claim, 0));
+		assert_ok!(Poe::attest_on_behalf(Origin::signed(9), claim.clone(), 1, signature.clone()));
+		assert_ok!(Poe::revoke_claim(Origin::signed(1), claim.clone()));
+		assert_noop!(
+			Poe::attest_on_behalf(Origin::signed(9), claim, 1, signature),
+			<Error<Test>>::BadSignature
+		);
+	});
+}
+
+#[test]
+fn should_return_bad_signature_error_when_attest_on_behalf_with_wrong_signer() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		let signature = TestSignature(2, Poe::attest_payload(&claim, 0));
+		assert_noop!(
+			Poe::attest_on_behalf(Origin::signed(9), claim, 1, signature),
+			<Error<Test>>::BadSignature
+		);
+	});
+}
+
+#[test]
+fn claims_owned_should_follow_owner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Poe::create_claim(Origin::signed(1), b"1".to_vec(), None));
+		assert_ok!(Poe::create_claim(Origin::signed(1), b"2".to_vec(), Some(3)));
+		let mut owned = Poe::claims_page(&1, None);
+		owned.sort();
+		assert_eq!(owned, vec![b"1".to_vec(), b"2".to_vec()]);
+
+		assert_ok!(Poe::transfer_claim(Origin::signed(1), 2, b"1".to_vec()));
+		assert_ok!(Poe::accept_claim(Origin::signed(2), b"1".to_vec()));
+		assert_eq!(Poe::claims_page(&1, None), vec![b"2".to_vec()]);
+		assert_eq!(Poe::claims_page(&2, None), vec![b"1".to_vec()]);
+
+		System::set_block_number(3);
+		Poe::on_initialize(3);
+		assert!(Poe::claims_page(&1, None).is_empty());
+
+		assert_ok!(Poe::revoke_claim(Origin::signed(2), b"1".to_vec()));
+		assert!(Poe::claims_page(&2, None).is_empty());
+	});
+}
+
+#[test]
+fn claims_page_should_paginate() {
+	new_test_ext().execute_with(|| {
+		for i in 0..=CLAIMS_PER_PAGE {
+			assert_ok!(Poe::create_claim(Origin::signed(1), i.to_le_bytes().to_vec(), None));
+		}
+
+		let first = Poe::claims_page(&1, None);
+		assert_eq!(first.len(), CLAIMS_PER_PAGE as usize);
+		// Removing a proof already seen does not shift the next page.
+		assert_ok!(Poe::revoke_claim(Origin::signed(1), first[0].clone()));
+		let second = Poe::claims_page(&1, first.last().cloned());
+		assert_eq!(second.len(), 1);
+		assert!(!first.contains(&second[0]));
+		assert!(Poe::claims_page(&1, second.last().cloned()).is_empty());
+		assert!(Poe::claims_page(&2, None).is_empty());
+	});
+}
+
+#[test]
+fn verify_claim_should_ignore_expired_claims() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_eq!(Poe::verify_claim(&claim), None);
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), Some(3)));
+		assert_eq!(Poe::verify_claim(&claim).unwrap().owner, 1);
+
+		// Not pruned yet, but no longer valid.
+		System::set_block_number(3);
+		assert_eq!(Poe::verify_claim(&claim), None);
+	});
+}
+
+#[test]
+fn upheld_dispute_should_slash_disputer() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		let evidence = H256::repeat_byte(1);
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), None));
+		assert_ok!(Poe::dispute_claim(Origin::signed(2), claim.clone(), evidence));
+		assert_eq!(Balances::reserved_balance(2), 10);
+		assert_ok!(Poe::defend_claim(Origin::signed(1), claim.clone(), H256::repeat_byte(2)));
+		assert_eq!(Balances::reserved_balance(1), 10);
+
+		assert_noop!(
+			Poe::transfer_claim(Origin::signed(1), 3, claim.clone()),
+			<Error<Test>>::ClaimDisputed
+		);
+		assert_noop!(
+			Poe::resolve_dispute(Origin::signed(1), claim.clone(), true),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(Poe::resolve_dispute(Origin::root(), claim.clone(), true));
+		assert_eq!(Poe::proofs(&claim).unwrap().owner, 1);
+		assert_eq!(Poe::disputes(&claim), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 90);
+		System::assert_last_event(Event::Poe(crate::Event::ClaimUpheld(1, claim, 2)));
+	});
+}
+
+#[test]
+fn struck_dispute_should_remove_claim_and_slash_defender() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), Some(5)));
+		assert_ok!(Poe::dispute_claim(Origin::signed(2), claim.clone(), H256::repeat_byte(1)));
+		assert_ok!(Poe::defend_claim(Origin::signed(1), claim.clone(), H256::repeat_byte(2)));
+
+		assert_ok!(Poe::resolve_dispute(Origin::root(), claim.clone(), false));
+		assert_eq!(Poe::proofs(&claim), None);
+		assert!(Poe::claims_page(&1, None).is_empty());
+		assert!(Poe::expiring(5).is_empty());
+		assert_eq!(Balances::free_balance(1), 90);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(2), 100);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		System::assert_last_event(Event::Poe(crate::Event::ClaimStruck(1, claim, 2)));
+	});
+}
+
+#[test]
+fn disputed_claim_should_outlive_its_expiry() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), Some(3)));
+		assert_ok!(Poe::dispute_claim(Origin::signed(2), claim.clone(), H256::repeat_byte(1)));
+		assert_noop!(
+			Poe::renew_claim(Origin::signed(1), claim.clone(), None),
+			<Error<Test>>::ClaimDisputed
+		);
+
+		System::set_block_number(3);
+		Poe::on_initialize(3);
+		assert!(Poe::proofs(&claim).is_some());
+		assert!(Poe::disputes(&claim).is_some());
+		assert_eq!(Balances::reserved_balance(2), 10);
+
+		// Upholding the claim does not bring it back to life.
+		assert_ok!(Poe::resolve_dispute(Origin::root(), claim.clone(), true));
+		assert_eq!(Poe::proofs(&claim), None);
+		assert!(Poe::claims_page(&1, None).is_empty());
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 90);
+		System::assert_last_event(Event::Poe(crate::Event::ClaimExpired(1, claim)));
+	});
+}
+
+#[test]
+fn should_return_dispute_errors_when_dispute_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		let evidence = H256::repeat_byte(1);
+		assert_noop!(
+			Poe::dispute_claim(Origin::signed(2), claim.clone(), evidence),
+			<Error<Test>>::ProofNotExist
+		);
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), None));
+		assert_noop!(
+			Poe::dispute_claim(Origin::signed(1), claim.clone(), evidence),
+			<Error<Test>>::DisputeOwnClaim
+		);
+		assert_noop!(
+			Poe::dispute_claim(Origin::signed(3), claim.clone(), evidence),
+			<Error<Test>>::InsufficientBond
+		);
+		assert_ok!(Poe::dispute_claim(Origin::signed(2), claim.clone(), evidence));
+		assert_noop!(
+			Poe::dispute_claim(Origin::signed(2), claim.clone(), evidence),
+			<Error<Test>>::ClaimDisputed
+		);
+		assert_noop!(Poe::revoke_claim(Origin::signed(1), claim), <Error<Test>>::ClaimDisputed);
+	});
+}
+
+#[test]
+fn should_return_already_defended_error_when_defend_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		let evidence = H256::repeat_byte(1);
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), None));
+		assert_noop!(
+			Poe::defend_claim(Origin::signed(1), claim.clone(), evidence),
+			<Error<Test>>::DisputeNotExist
+		);
+		assert_ok!(Poe::dispute_claim(Origin::signed(2), claim.clone(), evidence));
+		assert_noop!(
+			Poe::defend_claim(Origin::signed(2), claim.clone(), evidence),
+			<Error<Test>>::NotClaimOwner
+		);
+		assert_ok!(Poe::defend_claim(Origin::signed(1), claim.clone(), evidence));
+		assert_noop!(
+			Poe::defend_claim(Origin::signed(1), claim, evidence),
+			<Error<Test>>::AlreadyDefended
+		);
+	});
+}
+
+#[test]
+fn transfer_claim_should_wait_for_acceptance() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), None));
+		assert_ok!(Poe::transfer_claim(Origin::signed(1), 2, claim.clone()));
+		assert_eq!(Poe::pending_transfers(&claim), Some(2));
+		assert_eq!(Poe::proofs(&claim).unwrap().owner, 1);
+		System::assert_last_event(Event::Poe(crate::Event::TransferOffered(1, 2, claim.clone())));
+
+		assert_noop!(
+			Poe::accept_claim(Origin::signed(3), claim.clone()),
+			<Error<Test>>::NotTransferRecipient
+		);
+		assert_ok!(Poe::accept_claim(Origin::signed(2), claim.clone()));
+		assert_eq!(Poe::proofs(&claim).unwrap().owner, 2);
+		assert_eq!(Poe::pending_transfers(&claim), None);
+		System::assert_last_event(Event::Poe(crate::Event::ClaimTransferred(1, 2, claim)));
+	});
+}
+
+#[test]
+fn cancel_transfer_should_withdraw_offer() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), None));
+		assert_ok!(Poe::transfer_claim(Origin::signed(1), 2, claim.clone()));
+		assert_noop!(
+			Poe::cancel_transfer(Origin::signed(2), claim.clone()),
+			<Error<Test>>::NotClaimOwner
+		);
+		assert_ok!(Poe::cancel_transfer(Origin::signed(1), claim.clone()));
+		System::assert_last_event(Event::Poe(crate::Event::TransferCancelled(1, 2, claim.clone())));
+
+		assert_noop!(
+			Poe::accept_claim(Origin::signed(2), claim.clone()),
+			<Error<Test>>::TransferNotExist
+		);
+		assert_noop!(
+			Poe::cancel_transfer(Origin::signed(1), claim),
+			<Error<Test>>::TransferNotExist
+		);
+	});
+}
+
+#[test]
+fn should_return_transfer_to_self_error_when_transfer_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), None));
+		assert_noop!(
+			Poe::transfer_claim(Origin::signed(1), 1, claim),
+			<Error<Test>>::TransferToSelf
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_should_drop_pending_transfer() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_ok!(Poe::create_claim(Origin::signed(1), claim.clone(), None));
+		assert_ok!(Poe::transfer_claim(Origin::signed(1), 2, claim.clone()));
+		assert_ok!(Poe::revoke_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(Poe::pending_transfers(&claim), None);
+	});
+}
+
+#[test]
+fn create_revision_should_work() {
+	new_test_ext().execute_with(|| {
+		let (v0, v1, v2) = (b"v0".to_vec(), b"v1".to_vec(), b"v2".to_vec());
+		assert_ok!(Poe::create_claim(Origin::signed(1), v0.clone(), None));
+		assert_ok!(Poe::create_revision(Origin::signed(1), v0.clone(), v1.clone()));
+		System::assert_last_event(Event::Poe(crate::Event::ClaimRevised(
+			1,
+			v0.clone(),
+			v1.clone(),
+			1,
+		)));
+		assert_ok!(Poe::create_revision(Origin::signed(1), v1.clone(), v2.clone()));
+		assert_eq!(Poe::revisions(&v2).unwrap().version, 2);
+
+		let history = vec![v0.clone(), v1.clone(), v2.clone()];
+		assert_eq!(Poe::revision_history(&v0), history);
+		assert_eq!(Poe::revision_history(&v1), history);
+		assert_eq!(Poe::revision_history(&v2), history);
+	});
+}
+
+#[test]
+fn removed_claim_should_cut_revision_history() {
+	new_test_ext().execute_with(|| {
+		let (v0, v1, v2) = (b"v0".to_vec(), b"v1".to_vec(), b"v2".to_vec());
+		assert_ok!(Poe::create_claim(Origin::signed(1), v0.clone(), None));
+		assert_ok!(Poe::create_revision(Origin::signed(1), v0.clone(), v1.clone()));
+		assert_ok!(Poe::create_revision(Origin::signed(1), v1.clone(), v2.clone()));
+
+		assert_ok!(Poe::revoke_claim(Origin::signed(1), v1.clone()));
+		assert_eq!(Poe::revisions(&v1), None);
+		assert_eq!(Poe::successors(&v1), None);
+		assert_eq!(Poe::successors(&v0), None);
+		assert_eq!(Poe::revisions(&v2).unwrap().parent, None);
+		assert_eq!(Poe::revisions(&v2).unwrap().version, 2);
+		assert_eq!(Poe::revision_history(&v0), vec![v0.clone()]);
+		assert_eq!(Poe::revision_history(&v2), vec![v2]);
+
+		// Re-creating the removed claim does not link it back.
+		assert_ok!(Poe::create_claim(Origin::signed(1), v1.clone(), None));
+		assert_eq!(Poe::revision_history(&v1), vec![v1]);
+	});
+}
+
+#[test]
+fn should_return_not_claim_owner_error_when_create_revision() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Poe::create_claim(Origin::signed(1), b"v0".to_vec(), None));
+		assert_noop!(
+			Poe::create_revision(Origin::signed(2), b"v0".to_vec(), b"v1".to_vec()),
+			<Error<Test>>::NotClaimOwner
+		);
+		assert_noop!(
+			Poe::create_revision(Origin::signed(1), b"v9".to_vec(), b"v1".to_vec()),
+			<Error<Test>>::ProofNotExist
+		);
+	});
+}
+
+#[test]
+fn should_return_already_revised_error_when_create_revision() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Poe::create_claim(Origin::signed(1), b"v0".to_vec(), None));
+		assert_ok!(Poe::create_revision(Origin::signed(1), b"v0".to_vec(), b"v1".to_vec()));
+		assert_noop!(
+			Poe::create_revision(Origin::signed(1), b"v0".to_vec(), b"v1b".to_vec()),
+			<Error<Test>>::AlreadyRevised
+		);
+
+		// Revoking the newer version lets the parent be revised again.
+		assert_ok!(Poe::revoke_claim(Origin::signed(1), b"v1".to_vec()));
+		assert_ok!(Poe::create_revision(Origin::signed(1), b"v0".to_vec(), b"v1b".to_vec()));
+		assert_eq!(Poe::revision_history(&b"v0".to_vec()), vec![b"v0".to_vec(), b"v1b".to_vec()]);
+	});
+}
+
+#[test]
+fn should_return_too_many_revisions_error_when_create_revision() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Poe::create_claim(Origin::signed(1), b"v0".to_vec(), None));
+		assert_ok!(Poe::create_revision(Origin::signed(1), b"v0".to_vec(), b"v1".to_vec()));
+		assert_ok!(Poe::create_revision(Origin::signed(1), b"v1".to_vec(), b"v2".to_vec()));
+		// Make room in the owner index; the version count carries on.
+		assert_ok!(Poe::revoke_claim(Origin::signed(1), b"v0".to_vec()));
+		assert_ok!(Poe::create_revision(Origin::signed(1), b"v2".to_vec(), b"v3".to_vec()));
+		assert_ok!(Poe::revoke_claim(Origin::signed(1), b"v1".to_vec()));
+		assert_noop!(
+			Poe::create_revision(Origin::signed(1), b"v3".to_vec(), b"v4".to_vec()),
+			<Error<Test>>::TooManyRevisions
+		);
+	});
+}
+
+#[test]
+fn merkle_root_should_work() {
+	new_test_ext().execute_with(|| {
+		let (a, b, c) = (H256::repeat_byte(1), H256::repeat_byte(2), H256::repeat_byte(3));
+		let ab = BlakeTwo256::hash_of(&(a, b));
+		assert_eq!(Poe::merkle_root(&[]), H256::zero());
+		assert_eq!(Poe::merkle_root(&[a]), a);
+		assert_eq!(Poe::merkle_root(&[a, b]), ab);
+		assert_eq!(Poe::merkle_root(&[a, b, c]), BlakeTwo256::hash_of(&(ab, c)));
+	});
+}
+
+#[test]
+fn anchor_batch_should_work() {
+	new_test_ext().execute_with(|| {
+		let root = H256::repeat_byte(1);
+		assert_noop!(Poe::anchor_batch(Origin::signed(1), root, 0), <Error<Test>>::EmptyBatch);
+		assert_ok!(Poe::anchor_batch(Origin::signed(1), root, 3));
+		assert_eq!(Poe::anchors(root).unwrap().leaves, 3);
+		System::assert_last_event(Event::Poe(crate::Event::BatchAnchored(1, root, 3)));
+		assert_noop!(
+			Poe::anchor_batch(Origin::signed(1), root, 3),
+			<Error<Test>>::AnchorAlreadyExist
+		);
+	});
+}
+
+#[test]
+fn should_return_bad_origin_error_when_anchor_batch() {
+	new_test_ext().execute_with(|| {
+		let root = H256::repeat_byte(1);
+		assert_noop!(Poe::anchor_batch(Origin::signed(2), root, 3), DispatchError::BadOrigin);
+		assert_noop!(Poe::anchor_batch(Origin::none(), root, 3), DispatchError::BadOrigin);
+		assert_eq!(Poe::anchors(root), None);
+	});
+}
+
+#[test]
+fn offchain_worker_should_anchor_fetched_batch() {
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	let leaves = vec![H256::repeat_byte(1), H256::repeat_byte(2), H256::repeat_byte(3)];
+	offchain_state.write().expect_request(testing::PendingRequest {
+		method: "GET".into(),
+		uri: ANCHOR_ENDPOINT.into(),
+		response: Some(leaves.iter().flat_map(|leaf| leaf.as_bytes().to_vec()).collect()),
+		sent: true,
+		..Default::default()
+	});
+
+	ext.execute_with(|| {
+		UintAuthorityId::set_all_keys(vec![7u64]);
+		Poe::offchain_worker(2);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, Some((7, ())));
+		assert_eq!(
+			tx.call,
+			Call::Poe(crate::Call::anchor_batch { root: Poe::merkle_root(&leaves), leaves: 3 })
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_should_only_run_every_anchor_interval() {
+	let (offchain, _) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		UintAuthorityId::set_all_keys(vec![7u64]);
+		// No request is expected by the HTTP mock, so fetching would panic.
+		Poe::offchain_worker(3);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn offchain_worker_should_do_nothing_without_key() {
+	let (offchain, _) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		Poe::offchain_worker(2);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn claims_should_be_attributed_to_judged_identities() {
+	new_test_ext().execute_with(|| {
+		let claim = b"12345678".to_vec();
+		assert_ok!(Poe::create_claim(Origin::signed(7), claim.clone(), None));
+		System::assert_last_event(Event::Poe(crate::Event::ClaimAttributed(
+			7,
+			claim.clone(),
+			b"Alice".to_vec(),
+		)));
+		let details = Poe::claim_details(&claim).unwrap();
+		assert_eq!(details.info.owner, 7);
+		assert_eq!(details.owner_display_name, Some(b"Alice".to_vec()));
+
+		// Owners without a judged identity stay anonymous.
+		let other = b"other".to_vec();
+		assert_ok!(Poe::create_claim(Origin::signed(1), other.clone(), None));
+		System::assert_last_event(Event::Poe(crate::Event::ClaimCreated(1, other.clone())));
+		assert_eq!(Poe::claim_details(&other).unwrap().owner_display_name, None);
+		assert_eq!(Poe::claim_details(&b"missing".to_vec()), None);
+
+		// The claim is attributed again when it changes hands.
+		assert_ok!(Poe::transfer_claim(Origin::signed(1), 7, other.clone()));
+		assert_ok!(Poe::accept_claim(Origin::signed(7), other.clone()));
+		System::assert_last_event(Event::Poe(crate::Event::ClaimAttributed(
+			7,
+			other.clone(),
+			b"Alice".to_vec(),
+		)));
+		assert_eq!(Poe::claim_details(&other).unwrap().owner_display_name, Some(b"Alice".to_vec()));
+	});
+}
+
+#[test]
+fn migration_to_v1_should_convert_proofs_and_index_them_by_owner() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Poe>();
+		// Proofs were stored as `(owner, created_at)` before version 1.
+		let (claim, other) = (b"1".to_vec(), b"2".to_vec());
+		let key = |claim: &Vec<u8>| Proofs::<Test>::hashed_key_for(claim);
+		frame_support::storage::unhashed::put(&key(&claim), &(1u64, 3u64));
+		frame_support::storage::unhashed::put(&key(&other), &(2u64, 4u64));
+
+		Poe::on_runtime_upgrade();
+		assert_eq!(Poe::on_chain_storage_version(), 1);
+		assert_eq!(
+			Poe::proofs(&claim),
+			Some(ClaimInfo { owner: 1, created_at: 3, expires_at: None })
+		);
+		assert_eq!(
+			Poe::proofs(&other),
+			Some(ClaimInfo { owner: 2, created_at: 4, expires_at: None })
+		);
+		assert_eq!(Poe::claims_page(&1, None), vec![claim.clone()]);
+		assert_eq!(Poe::claims_page(&2, None), vec![other]);
 
+		// Upgrading again leaves the migrated proofs alone.
+		Poe::on_runtime_upgrade();
+		assert_eq!(Poe::proofs(&claim).unwrap().owner, 1);
 	});
 }
@@ -0,0 +1,308 @@
+//! Weights for pallet_poe
+//!
+//! These are estimates pending a run of the Substrate benchmark CLI on reference hardware.
+//! Regenerate this file with:
+//!
+//! ./target/release/node-template benchmark --chain=dev --steps=50 --repeat=20
+//! --pallet=pallet_poe --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --heap-pages=4096 --output=./pallets/poe/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_poe.
+pub trait WeightInfo {
+	fn create_claim(s: u32, ) -> Weight;
+	fn revoke_claim(s: u32, ) -> Weight;
+	fn transfer_claim(s: u32, ) -> Weight;
+	fn accept_claim(s: u32, ) -> Weight;
+	fn renew_claim(s: u32, ) -> Weight;
+	fn create_revision(s: u32, ) -> Weight;
+	fn create_cosigned_claim(s: u32, n: u32, ) -> Weight;
+	fn approve_claim(s: u32, ) -> Weight;
+	fn attest_on_behalf(s: u32, ) -> Weight;
+	fn dispute_claim(s: u32, ) -> Weight;
+	fn defend_claim(s: u32, ) -> Weight;
+	fn resolve_dispute(s: u32, ) -> Weight;
+	fn cancel_transfer(s: u32, ) -> Weight;
+	fn anchor_batch() -> Weight;
+	fn on_initialize(e: u32, p: u32, ) -> Weight;
+}
+
+/// Weights for pallet_poe using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Poe Proofs (r:1 w:1)
+	// Storage: Poe PendingClaims (r:1 w:0)
+	// Storage: Poe Expiring (r:1 w:1)
+	// Storage: Poe ClaimsOwned (r:0 w:1)
+	// Storage: Identity IdentityOf (r:1 w:0)
+	fn create_claim(s: u32, ) -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Poe Proofs (r:1 w:1)
+	// Storage: Poe Disputes (r:1 w:1)
+	// Storage: Poe Expiring (r:1 w:1)
+	// Storage: Poe Successors (r:2 w:2)
+	// Storage: Poe Revisions (r:2 w:2)
+	// Storage: Poe CoSigners (r:0 w:1)
+	// Storage: Poe PendingTransfers (r:0 w:1)
+	// Storage: Poe ClaimsOwned (r:0 w:1)
+	fn revoke_claim(s: u32, ) -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add((9_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	// Storage: Poe Proofs (r:1 w:0)
+	// Storage: Poe Disputes (r:1 w:0)
+	// Storage: Poe PendingTransfers (r:0 w:1)
+	fn transfer_claim(s: u32, ) -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Poe PendingTransfers (r:1 w:1)
+	// Storage: Poe Disputes (r:1 w:0)
+	// Storage: Poe Proofs (r:1 w:1)
+	// Storage: Poe ClaimsOwned (r:0 w:2)
+	// Storage: Identity IdentityOf (r:1 w:0)
+	fn accept_claim(s: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Poe Proofs (r:1 w:1)
+	// Storage: Poe Disputes (r:1 w:0)
+	// Storage: Poe Expiring (r:2 w:2)
+	fn renew_claim(s: u32, ) -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Poe Proofs (r:2 w:1)
+	// Storage: Poe Successors (r:1 w:1)
+	// Storage: Poe Revisions (r:1 w:1)
+	// Storage: Poe PendingClaims (r:1 w:0)
+	// Storage: Poe ClaimsOwned (r:0 w:1)
+	// Storage: Identity IdentityOf (r:1 w:0)
+	fn create_revision(s: u32, ) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add((6_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Poe Proofs (r:1 w:0)
+	// Storage: Poe PendingClaims (r:1 w:1)
+	// Storage: Poe PendingDeadlines (r:1 w:1)
+	fn create_cosigned_claim(s: u32, n: u32, ) -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((600_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Poe PendingClaims (r:1 w:1)
+	// Storage: Poe ClaimsOwned (r:0 w:1)
+	// Storage: Poe PendingDeadlines (r:1 w:1)
+	// Storage: Poe Proofs (r:0 w:1)
+	// Storage: Poe CoSigners (r:0 w:1)
+	// Storage: Identity IdentityOf (r:1 w:0)
+	fn approve_claim(s: u32, ) -> Weight {
+		(44_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Poe AttestNonces (r:1 w:1)
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: Poe Proofs (r:1 w:1)
+	// Storage: Poe PendingClaims (r:1 w:0)
+	// Storage: Poe ClaimsOwned (r:0 w:1)
+	// Storage: Identity IdentityOf (r:1 w:0)
+	fn attest_on_behalf(s: u32, ) -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add((5_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Poe Proofs (r:1 w:0)
+	// Storage: Poe Disputes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn dispute_claim(s: u32, ) -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Poe Proofs (r:1 w:0)
+	// Storage: Poe Disputes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn defend_claim(s: u32, ) -> Weight {
+		(37_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Poe Proofs (r:1 w:1)
+	// Storage: Poe Disputes (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: Poe Expiring (r:1 w:1)
+	// Storage: Poe Successors (r:2 w:2)
+	// Storage: Poe Revisions (r:2 w:2)
+	// Storage: Poe CoSigners (r:0 w:1)
+	// Storage: Poe PendingTransfers (r:0 w:1)
+	// Storage: Poe ClaimsOwned (r:0 w:1)
+	fn resolve_dispute(s: u32, ) -> Weight {
+		(78_000_000 as Weight)
+			.saturating_add((9_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	// Storage: Poe Proofs (r:1 w:0)
+	// Storage: Poe PendingTransfers (r:1 w:1)
+	fn cancel_transfer(s: u32, ) -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Staking Ledger (r:1 w:0)
+	// Storage: Session Validators (r:1 w:0)
+	// Storage: Poe Anchors (r:1 w:1)
+	fn anchor_batch() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Poe Expiring (r:1 w:1)
+	// Storage: Poe PendingDeadlines (r:1 w:1)
+	// Storage: Poe Disputes (r:1 w:1)
+	// Storage: Poe Proofs (r:1 w:1)
+	// Storage: Poe Successors (r:2 w:2)
+	// Storage: Poe Revisions (r:2 w:2)
+	// Storage: Poe CoSigners (r:0 w:1)
+	// Storage: Poe PendingTransfers (r:0 w:1)
+	// Storage: Poe ClaimsOwned (r:0 w:1)
+	// Storage: Poe PendingClaims (r:1 w:1)
+	fn on_initialize(e: u32, p: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((46_000_000 as Weight).saturating_mul(e as Weight))
+			.saturating_add((9_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(e as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((9 as Weight).saturating_mul(e as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_claim(s: u32, ) -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn revoke_claim(s: u32, ) -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add((9_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	fn transfer_claim(s: u32, ) -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn accept_claim(s: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn renew_claim(s: u32, ) -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn create_revision(s: u32, ) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add((6_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn create_cosigned_claim(s: u32, n: u32, ) -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((600_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn approve_claim(s: u32, ) -> Weight {
+		(44_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn attest_on_behalf(s: u32, ) -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add((5_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn dispute_claim(s: u32, ) -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn defend_claim(s: u32, ) -> Weight {
+		(37_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn resolve_dispute(s: u32, ) -> Weight {
+		(78_000_000 as Weight)
+			.saturating_add((9_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
+	}
+	fn cancel_transfer(s: u32, ) -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn anchor_batch() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn on_initialize(e: u32, p: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((46_000_000 as Weight).saturating_mul(e as Weight))
+			.saturating_add((9_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(e as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((9 as Weight).saturating_mul(e as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+}
@@ -0,0 +1,75 @@
+//! Benchmarking setup for pallet-preimage
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Preimage;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, EnsureOrigin, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Hash};
+use sp_std::{prelude::*, vec};
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// A caller with enough to note the largest preimage.
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	caller
+}
+
+/// The largest preimage, and its hash.
+fn preimage<T: Config>() -> (Vec<u8>, T::Hash) {
+	let preimage = vec![1; T::MaxSize::get() as usize];
+	let hash = T::Hashing::hash(&preimage);
+	(preimage, hash)
+}
+
+benchmarks! {
+	// `s` is the size of the preimage.
+	note_preimage {
+		let s in 0 .. T::MaxSize::get();
+		let caller = funded_caller::<T>();
+		let bytes = vec![1; s as usize];
+		let hash = T::Hashing::hash(&bytes);
+	}: _(RawOrigin::Signed(caller), bytes)
+	verify {
+		assert!(Preimage::<T>::have_preimage(&hash));
+	}
+
+	// The worst case returns a deposit.
+	unnote_preimage {
+		let caller = funded_caller::<T>();
+		let (bytes, hash) = preimage::<T>();
+		Preimage::<T>::note_preimage(RawOrigin::Signed(caller.clone()).into(), bytes)?;
+	}: _(RawOrigin::Signed(caller), hash)
+	verify {
+		assert!(!Preimage::<T>::have_preimage(&hash));
+	}
+
+	// The worst case returns a deposit.
+	request_preimage {
+		let origin = T::ManagerOrigin::successful_origin();
+		let caller = funded_caller::<T>();
+		let (bytes, hash) = preimage::<T>();
+		Preimage::<T>::note_preimage(RawOrigin::Signed(caller).into(), bytes)?;
+	}: _<T::Origin>(origin, hash)
+	verify {
+		assert_eq!(Preimage::<T>::status_for(&hash), Some(RequestStatus::Requested(1)));
+	}
+
+	// The worst case withdraws the last request, removing the preimage.
+	unrequest_preimage {
+		let origin = T::ManagerOrigin::successful_origin();
+		let (bytes, hash) = preimage::<T>();
+		Preimage::<T>::request_preimage(origin.clone(), hash)?;
+		Preimage::<T>::note_preimage(origin.clone(), bytes)?;
+	}: _<T::Origin>(origin, hash)
+	verify {
+		assert!(!Preimage::<T>::have_preimage(&hash));
+	}
+
+	impl_benchmark_test_suite!(Preimage, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,267 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Stores the preimages of hashes, e.g. of calls to be dispatched later, so that only their
+/// hash has to be passed around.
+///
+/// A backport of `pallet_preimage` from later Substrate releases. Anyone may note a preimage
+/// for a deposit; once the chain requests the hash through `ManagerOrigin`, the deposit is
+/// returned and the preimage is kept until every request is withdrawn.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use crate::WeightInfo;
+	use frame_support::{
+		dispatch::{DispatchResult, DispatchResultWithPostInfo},
+		pallet_prelude::*,
+		traits::{Currency, ReservableCurrency},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Hash, Saturating};
+	use sp_std::prelude::*;
+
+	type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// Whether a preimage is requested by the chain, and who holds a deposit for it if not.
+	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+	pub enum RequestStatus<AccountId, Balance> {
+		/// The hash is not requested. The account which noted the preimage, if not
+		/// `ManagerOrigin`, holds the given deposit until it unnotes it or the hash is requested.
+		Unrequested(Option<(AccountId, Balance)>),
+		/// The hash is requested this many times. Its preimage, if noted, is kept until every
+		/// request is withdrawn.
+		Requested(u32),
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The origin which may request hashes, and note and unnote preimages without a deposit.
+		type ManagerOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum size of a preimage, in bytes.
+		#[pallet::constant]
+		type MaxSize: Get<u32>;
+
+		/// The deposit for noting a preimage, regardless of its size.
+		#[pallet::constant]
+		type BaseDeposit: Get<BalanceOf<Self>>;
+
+		/// The deposit for noting a preimage, per byte.
+		#[pallet::constant]
+		type ByteDeposit: Get<BalanceOf<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// The request status of the hashes noted or requested.
+	#[pallet::storage]
+	#[pallet::getter(fn status_for)]
+	pub type StatusFor<T: Config> =
+		StorageMap<_, Identity, T::Hash, RequestStatus<T::AccountId, BalanceOf<T>>>;
+
+	/// The preimages of the hashes noted.
+	#[pallet::storage]
+	#[pallet::getter(fn preimage_for)]
+	pub type PreimageFor<T: Config> = StorageMap<_, Identity, T::Hash, BoundedVec<u8, T::MaxSize>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A preimage was noted. \[hash\]
+		Noted(T::Hash),
+		/// A hash was requested. \[hash\]
+		Requested(T::Hash),
+		/// A preimage was removed. \[hash\]
+		Cleared(T::Hash),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The preimage is larger than `MaxSize`.
+		TooLarge,
+		/// The preimage is already noted.
+		AlreadyNoted,
+		/// The caller did not note the preimage.
+		NotAuthorized,
+		/// The preimage is not noted.
+		NotNoted,
+		/// The hash is requested, so its preimage cannot be unnoted.
+		Requested,
+		/// The hash is not requested.
+		NotRequested,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Note `bytes` as a preimage, reserving a deposit unless the caller is `ManagerOrigin`.
+		/// Noting the preimage of a requested hash is free.
+		#[pallet::weight(T::WeightInfo::note_preimage(bytes.len() as u32))]
+		pub fn note_preimage(origin: OriginFor<T>, bytes: Vec<u8>) -> DispatchResultWithPostInfo {
+			let maybe_sender = Self::ensure_signed_or_manager(origin)?;
+			let requested = Self::note_bytes(bytes, maybe_sender.as_ref())?;
+			if requested || maybe_sender.is_none() {
+				Ok(Pays::No.into())
+			} else {
+				Ok(().into())
+			}
+		}
+
+		/// Remove the preimage of `hash`, which is not requested, returning its deposit. Only the
+		/// account which noted it, or `ManagerOrigin`, may remove it.
+		#[pallet::weight(T::WeightInfo::unnote_preimage())]
+		pub fn unnote_preimage(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+			let maybe_sender = Self::ensure_signed_or_manager(origin)?;
+			Self::do_unnote_preimage(&hash, maybe_sender)
+		}
+
+		/// Request the preimage of `hash`, so that it is kept. Its deposit, if any, is returned.
+		#[pallet::weight(T::WeightInfo::request_preimage())]
+		pub fn request_preimage(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			Self::do_request_preimage(&hash);
+			Ok(())
+		}
+
+		/// Withdraw a request for the preimage of `hash`. The preimage is removed along with the
+		/// last request.
+		#[pallet::weight(T::WeightInfo::unrequest_preimage())]
+		pub fn unrequest_preimage(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			Self::do_unrequest_preimage(&hash)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The signer of `origin`, or `None` if it is `ManagerOrigin`.
+		fn ensure_signed_or_manager(
+			origin: OriginFor<T>,
+		) -> Result<Option<T::AccountId>, DispatchError> {
+			if T::ManagerOrigin::ensure_origin(origin.clone()).is_ok() {
+				return Ok(None)
+			}
+			Ok(Some(ensure_signed(origin)?))
+		}
+
+		/// Store `preimage`, reserving a deposit from `maybe_depositor` unless its hash is
+		/// requested. Returns whether the hash is requested.
+		fn note_bytes(
+			preimage: Vec<u8>,
+			maybe_depositor: Option<&T::AccountId>,
+		) -> Result<bool, DispatchError> {
+			let preimage: BoundedVec<u8, T::MaxSize> =
+				preimage.try_into().map_err(|_| <Error<T>>::TooLarge)?;
+			let hash = T::Hashing::hash(&preimage);
+			ensure!(!<PreimageFor<T>>::contains_key(hash), <Error<T>>::AlreadyNoted);
+
+			let status = match (<StatusFor<T>>::get(hash), maybe_depositor) {
+				(Some(status @ RequestStatus::Requested(..)), _) => status,
+				(Some(RequestStatus::Unrequested(..)), _) =>
+					return Err(<Error<T>>::AlreadyNoted.into()),
+				(None, None) => RequestStatus::Unrequested(None),
+				(None, Some(depositor)) => {
+					let deposit = T::BaseDeposit::get()
+						.saturating_add(T::ByteDeposit::get() * (preimage.len() as u32).into());
+					T::Currency::reserve(depositor, deposit)?;
+					RequestStatus::Unrequested(Some((depositor.clone(), deposit)))
+				},
+			};
+			let requested = matches!(status, RequestStatus::Requested(..));
+			<StatusFor<T>>::insert(hash, status);
+			<PreimageFor<T>>::insert(hash, preimage);
+
+			Self::deposit_event(Event::Noted(hash));
+			Ok(requested)
+		}
+
+		/// Request the preimage of `hash`, returning the deposit of the account which noted it.
+		pub fn do_request_preimage(hash: &T::Hash) {
+			let count = match <StatusFor<T>>::get(hash) {
+				Some(RequestStatus::Requested(count)) => count.saturating_add(1),
+				Some(RequestStatus::Unrequested(Some((owner, deposit)))) => {
+					T::Currency::unreserve(&owner, deposit);
+					1
+				},
+				Some(RequestStatus::Unrequested(None)) | None => 1,
+			};
+			<StatusFor<T>>::insert(hash, RequestStatus::Requested(count));
+			if count == 1 {
+				Self::deposit_event(Event::Requested(*hash));
+			}
+		}
+
+		/// Remove the preimage of `hash`, checking it was noted by `maybe_check_owner` if any.
+		fn do_unnote_preimage(
+			hash: &T::Hash,
+			maybe_check_owner: Option<T::AccountId>,
+		) -> DispatchResult {
+			match <StatusFor<T>>::get(hash).ok_or(<Error<T>>::NotNoted)? {
+				RequestStatus::Unrequested(Some((owner, deposit))) => {
+					ensure!(
+						maybe_check_owner.map_or(true, |who| who == owner),
+						<Error<T>>::NotAuthorized
+					);
+					T::Currency::unreserve(&owner, deposit);
+				},
+				RequestStatus::Unrequested(None) => {
+					ensure!(maybe_check_owner.is_none(), <Error<T>>::NotAuthorized);
+				},
+				RequestStatus::Requested(_) => return Err(<Error<T>>::Requested.into()),
+			}
+			<StatusFor<T>>::remove(hash);
+			<PreimageFor<T>>::remove(hash);
+			Self::deposit_event(Event::Cleared(*hash));
+			Ok(())
+		}
+
+		/// Withdraw a request for the preimage of `hash`, removing it with the last request.
+		pub fn do_unrequest_preimage(hash: &T::Hash) -> DispatchResult {
+			match <StatusFor<T>>::get(hash).ok_or(<Error<T>>::NotRequested)? {
+				RequestStatus::Requested(count) if count > 1 => {
+					<StatusFor<T>>::insert(hash, RequestStatus::Requested(count - 1));
+				},
+				RequestStatus::Requested(_) => {
+					<StatusFor<T>>::remove(hash);
+					if <PreimageFor<T>>::take(hash).is_some() {
+						Self::deposit_event(Event::Cleared(*hash));
+					}
+				},
+				RequestStatus::Unrequested(_) => return Err(<Error<T>>::NotRequested.into()),
+			}
+			Ok(())
+		}
+
+		/// Whether the preimage of `hash` is noted.
+		pub fn have_preimage(hash: &T::Hash) -> bool {
+			<PreimageFor<T>>::contains_key(hash)
+		}
+
+		/// The preimage of `hash`, if noted.
+		pub fn get_preimage(hash: &T::Hash) -> Option<Vec<u8>> {
+			<PreimageFor<T>>::get(hash).map(|preimage| preimage.into_inner())
+		}
+	}
+}
@@ -0,0 +1,126 @@
+use crate::{mock::*, Error, RequestStatus};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+#[test]
+fn note_preimage_should_reserve_a_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(1), vec![1; 3]));
+		let hash = hash(&[1; 3]);
+		assert_eq!(Preimage::get_preimage(&hash), Some(vec![1; 3]));
+		// The base deposit of 2 and 1 per byte.
+		assert_eq!(Balances::reserved_balance(1), 5);
+		assert_eq!(Preimage::status_for(&hash), Some(RequestStatus::Unrequested(Some((1, 5)))));
+		System::assert_last_event(Event::Preimage(crate::Event::Noted(hash)));
+
+		assert_noop!(
+			Preimage::note_preimage(Origin::signed(2), vec![1; 3]),
+			Error::<Test>::AlreadyNoted
+		);
+		assert_noop!(
+			Preimage::note_preimage(Origin::signed(1), vec![1; 17]),
+			Error::<Test>::TooLarge
+		);
+	});
+}
+
+#[test]
+fn manager_should_note_preimages_without_a_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::root(), vec![1; 3]));
+		let hash = hash(&[1; 3]);
+		assert_eq!(Preimage::status_for(&hash), Some(RequestStatus::Unrequested(None)));
+
+		// Only the manager may unnote it.
+		assert_noop!(
+			Preimage::unnote_preimage(Origin::signed(1), hash),
+			Error::<Test>::NotAuthorized
+		);
+		assert_ok!(Preimage::unnote_preimage(Origin::root(), hash));
+		assert!(!Preimage::have_preimage(&hash));
+	});
+}
+
+#[test]
+fn unnote_preimage_should_return_the_deposit_to_the_noter_only() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(1), vec![1; 3]));
+		let hash = hash(&[1; 3]);
+		assert_noop!(
+			Preimage::unnote_preimage(Origin::signed(2), hash),
+			Error::<Test>::NotAuthorized
+		);
+
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(1), hash));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(!Preimage::have_preimage(&hash));
+		assert_eq!(Preimage::status_for(&hash), None);
+		System::assert_last_event(Event::Preimage(crate::Event::Cleared(hash)));
+		assert_noop!(Preimage::unnote_preimage(Origin::signed(1), hash), Error::<Test>::NotNoted);
+	});
+}
+
+#[test]
+fn request_preimage_should_return_the_deposit_and_keep_the_preimage() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(1), vec![1; 3]));
+		let hash = hash(&[1; 3]);
+		assert_noop!(Preimage::request_preimage(Origin::signed(1), hash), DispatchError::BadOrigin);
+
+		assert_ok!(Preimage::request_preimage(Origin::root(), hash));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Preimage::status_for(&hash), Some(RequestStatus::Requested(1)));
+		System::assert_last_event(Event::Preimage(crate::Event::Requested(hash)));
+		assert_noop!(Preimage::unnote_preimage(Origin::signed(1), hash), Error::<Test>::Requested);
+		assert_noop!(Preimage::unnote_preimage(Origin::root(), hash), Error::<Test>::Requested);
+	});
+}
+
+#[test]
+fn preimages_of_requested_hashes_should_be_noted_for_free() {
+	new_test_ext().execute_with(|| {
+		let hash = hash(&[1; 3]);
+		assert_ok!(Preimage::request_preimage(Origin::root(), hash));
+		assert!(!Preimage::have_preimage(&hash));
+
+		let info = Preimage::note_preimage(Origin::signed(2), vec![1; 3]).unwrap();
+		assert_eq!(info.pays_fee, frame_support::weights::Pays::No);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Preimage::get_preimage(&hash), Some(vec![1; 3]));
+		assert_eq!(Preimage::status_for(&hash), Some(RequestStatus::Requested(1)));
+	});
+}
+
+#[test]
+fn unrequest_preimage_should_remove_it_with_the_last_request() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(1), vec![1; 3]));
+		let hash = hash(&[1; 3]);
+		assert_ok!(Preimage::request_preimage(Origin::root(), hash));
+		assert_ok!(Preimage::request_preimage(Origin::root(), hash));
+		assert_eq!(Preimage::status_for(&hash), Some(RequestStatus::Requested(2)));
+
+		assert_ok!(Preimage::unrequest_preimage(Origin::root(), hash));
+		assert!(Preimage::have_preimage(&hash));
+		assert_ok!(Preimage::unrequest_preimage(Origin::root(), hash));
+		assert!(!Preimage::have_preimage(&hash));
+		assert_eq!(Preimage::status_for(&hash), None);
+		System::assert_last_event(Event::Preimage(crate::Event::Cleared(hash)));
+
+		assert_noop!(
+			Preimage::unrequest_preimage(Origin::root(), hash),
+			Error::<Test>::NotRequested
+		);
+	});
+}
+
+#[test]
+fn unrequest_preimage_should_fail_for_hashes_noted_only() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(1), vec![1; 3]));
+		assert_noop!(
+			Preimage::unrequest_preimage(Origin::root(), hash(&[1; 3])),
+			Error::<Test>::NotRequested
+		);
+	});
+}
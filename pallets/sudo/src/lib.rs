@@ -0,0 +1,165 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Lets a single account, the sudo key, dispatch calls as root or as any other account.
+///
+/// A backport of `pallet_sudo` from later Substrate releases, in which the key is optional:
+/// chains started without one have no account able to call `Sudo` at all, and root is only
+/// reachable through governance.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::{DispatchResult, DispatchResultWithPostInfo, UnfilteredDispatchable},
+		pallet_prelude::*,
+		weights::GetDispatchInfo,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::StaticLookup;
+	use sp_std::prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// A sudo-able call.
+		type Call: Parameter + UnfilteredDispatchable<Origin = Self::Origin> + GetDispatchInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// The sudo key, if any.
+	#[pallet::storage]
+	#[pallet::getter(fn key)]
+	pub(super) type Key<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The sudo key, or `None` to start the chain without one.
+		pub key: Option<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { key: None }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			if let Some(key) = &self.key {
+				<Key<T>>::put(key);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A sudo call just took place. \[result\]
+		Sudid(DispatchResult),
+		/// The sudo key changed hands; the old key is supplied. \[old_sudoer\]
+		KeyChanged(T::AccountId),
+		/// A call was dispatched as another account. \[result\]
+		SudoAsDone(DispatchResult),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The caller is not the sudo key, or there is no sudo key.
+		RequireSudo,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Dispatch `call` as root. Only the sudo key may call this, without paying fees.
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(dispatch_info.weight.saturating_add(10_000), dispatch_info.class)
+		})]
+		pub fn sudo(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_sudo(&sender)?;
+
+			let res = call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
+			Self::deposit_event(Event::Sudid(res.map(|_| ()).map_err(|e| e.error)));
+			Ok(Pays::No.into())
+		}
+
+		/// Dispatch `call` as root, charging `weight` for it instead of its own weight.
+		#[pallet::weight((*_weight, call.get_dispatch_info().class))]
+		pub fn sudo_unchecked_weight(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::Call>,
+			_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_sudo(&sender)?;
+
+			let res = call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
+			Self::deposit_event(Event::Sudid(res.map(|_| ()).map_err(|e| e.error)));
+			Ok(Pays::No.into())
+		}
+
+		/// Hand the sudo key over to `new`.
+		#[pallet::weight(0)]
+		pub fn set_key(
+			origin: OriginFor<T>,
+			new: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_sudo(&sender)?;
+			let new = T::Lookup::lookup(new)?;
+
+			Self::deposit_event(Event::KeyChanged(sender));
+			<Key<T>>::put(new);
+			Ok(Pays::No.into())
+		}
+
+		/// Dispatch `call` as signed by `who`.
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(
+				dispatch_info
+					.weight
+					.saturating_add(10_000)
+					.saturating_add(T::DbWeight::get().reads_writes(1, 1)),
+				dispatch_info.class,
+			)
+		})]
+		pub fn sudo_as(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			call: Box<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_sudo(&sender)?;
+			let who = T::Lookup::lookup(who)?;
+
+			let res = call.dispatch_bypass_filter(frame_system::RawOrigin::Signed(who).into());
+			Self::deposit_event(Event::SudoAsDone(res.map(|_| ()).map_err(|e| e.error)));
+			Ok(Pays::No.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn ensure_sudo(who: &T::AccountId) -> DispatchResult {
+			ensure!(Self::key().as_ref() == Some(who), Error::<T>::RequireSudo);
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,79 @@
+use crate::{mock::*, Error, Event as SudoEvent};
+use frame_support::{assert_noop, assert_ok, weights::Weight};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError,
+};
+
+fn set_storage(value: &[u8]) -> Box<Call> {
+	Box::new(Call::System(frame_system::Call::set_storage {
+		items: vec![(b"key".to_vec(), value.to_vec())],
+	}))
+}
+
+fn remark() -> Box<Call> {
+	Box::new(Call::System(frame_system::Call::remark_with_event { remark: b"hi".to_vec() }))
+}
+
+#[test]
+fn sudo_should_dispatch_as_root() {
+	new_test_ext(Some(1)).execute_with(|| {
+		assert_ok!(Sudo::sudo(Origin::signed(1), set_storage(b"1")));
+		assert_eq!(sp_io::storage::get(b"key"), Some(b"1".to_vec()));
+		System::assert_last_event(Event::Sudo(SudoEvent::Sudid(Ok(()))));
+
+		// The call failing does not fail the sudo call.
+		assert_ok!(Sudo::sudo(Origin::signed(1), remark()));
+		System::assert_last_event(Event::Sudo(SudoEvent::Sudid(Err(DispatchError::BadOrigin))));
+
+		let weight: Weight = 1_000;
+		assert_ok!(Sudo::sudo_unchecked_weight(Origin::signed(1), set_storage(b"2"), weight));
+		assert_eq!(sp_io::storage::get(b"key"), Some(b"2".to_vec()));
+	});
+}
+
+#[test]
+fn sudo_as_should_dispatch_as_the_given_account() {
+	new_test_ext(Some(1)).execute_with(|| {
+		assert_ok!(Sudo::sudo_as(Origin::signed(1), 2, remark()));
+		System::assert_has_event(Event::System(frame_system::Event::Remarked(
+			2,
+			BlakeTwo256::hash(b"hi"),
+		)));
+		System::assert_last_event(Event::Sudo(SudoEvent::SudoAsDone(Ok(()))));
+	});
+}
+
+#[test]
+fn set_key_should_hand_the_key_over() {
+	new_test_ext(Some(1)).execute_with(|| {
+		assert_ok!(Sudo::set_key(Origin::signed(1), 2));
+		assert_eq!(Sudo::key(), Some(2));
+		System::assert_last_event(Event::Sudo(SudoEvent::KeyChanged(1)));
+		assert_noop!(Sudo::sudo(Origin::signed(1), set_storage(b"1")), Error::<Test>::RequireSudo);
+		assert_ok!(Sudo::sudo(Origin::signed(2), set_storage(b"1")));
+	});
+}
+
+#[test]
+fn every_call_should_be_refused_without_a_key() {
+	new_test_ext(None).execute_with(|| {
+		assert_eq!(Sudo::key(), None);
+		for who in [0, 1] {
+			assert_noop!(
+				Sudo::sudo(Origin::signed(who), set_storage(b"1")),
+				Error::<Test>::RequireSudo
+			);
+			assert_noop!(
+				Sudo::sudo_unchecked_weight(Origin::signed(who), set_storage(b"1"), 0),
+				Error::<Test>::RequireSudo
+			);
+			assert_noop!(
+				Sudo::sudo_as(Origin::signed(who), 2, remark()),
+				Error::<Test>::RequireSudo
+			);
+			assert_noop!(Sudo::set_key(Origin::signed(who), who), Error::<Test>::RequireSudo);
+		}
+		assert_noop!(Sudo::sudo(Origin::root(), set_storage(b"1")), DispatchError::BadOrigin);
+	});
+}
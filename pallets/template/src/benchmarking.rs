@@ -4,16 +4,76 @@ use super::*;
 
 #[allow(unused)]
 use crate::Pallet as Template;
+use codec::Encode;
 use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::{
+	sp_runtime::traits::Bounded,
+	traits::{Currency, EnsureOrigin},
+};
 use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	caller
+}
 
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
+	set {
+		let k in 1 .. T::MaxKeyLength::get();
+		let v in 1 .. T::MaxValueLength::get();
+		let caller = funded_caller::<T>();
+		let key = vec![0u8; k as usize];
+	}: _(RawOrigin::Signed(caller.clone()), key.clone(), vec![1u8; v as usize])
+	verify {
+		assert_eq!(Template::<T>::entry_count(&caller), 1);
+	}
+
+	remove {
+		let k in 1 .. T::MaxKeyLength::get();
+		let caller = funded_caller::<T>();
+		let key = vec![0u8; k as usize];
+		let value = vec![1u8; T::MaxValueLength::get() as usize];
+		Template::<T>::set(RawOrigin::Signed(caller.clone()).into(), key.clone(), value)?;
+	}: _(RawOrigin::Signed(caller.clone()), key)
+	verify {
+		assert_eq!(Template::<T>::entry_count(&caller), 0);
+	}
+
+	clear_namespace {
+		let n in 1 .. T::MaxEntries::get();
+		let caller = funded_caller::<T>();
+		let value = vec![1u8; T::MaxValueLength::get() as usize];
+		for i in 0 .. n {
+			let key = i.encode();
+			Template::<T>::set(RawOrigin::Signed(caller.clone()).into(), key, value.clone())?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Template::<T>::entry_count(&caller), 0);
+	}
+
+	set_something {
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, 42)
+	verify {
+		assert_eq!(Something::<T>::get(), 42);
+	}
+
+	increment {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller))
+	verify {
+		assert_eq!(Something::<T>::get(), 1);
+	}
+
+	decrement {
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		Something::<T>::put(1);
+	}: _(RawOrigin::Signed(caller))
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert_eq!(Something::<T>::get(), 0);
 	}
 
 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
@@ -1,8 +1,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-/// Edit this file to define custom logic or remove it if it is not needed.
-/// Learn more about FRAME and the core library of Substrate FRAME pallets:
-/// <https://docs.substrate.io/v3/runtime/frame>
+/// A per-account namespaced key-value registry. Every entry reserves a deposit proportional to
+/// its size from the account owning the namespace, which is returned when the entry is
+/// removed.
+///
+/// The pallet also keeps `Something`, a counter shared across accounts, along with a bounded
+/// history of its latest changes.
 pub use pallet::*;
 
 #[cfg(test)]
@@ -14,89 +17,297 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod weights;
+pub use weights::WeightInfo;
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
+	use crate::WeightInfo;
+	use frame_support::{
+		dispatch::DispatchResult,
+		pallet_prelude::*,
+		sp_runtime::traits::{CheckedMul, Saturating, Zero},
+		traits::{Currency, ReservableCurrency},
+	};
 	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// A key of the registry.
+	pub type KeyOf<T> = BoundedVec<u8, <T as Config>::MaxKeyLength>;
+
+	/// A value stored in the registry, with the deposit reserved for it.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Entry<T: Config> {
+		pub value: BoundedVec<u8, T::MaxValueLength>,
+		pub deposit: BalanceOf<T>,
+	}
+
+	/// A change of `Something`.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct SomethingChange<AccountId, BlockNumber> {
+		/// The value after the change.
+		pub value: u32,
+		/// The block in which the change happened.
+		pub block: BlockNumber,
+		/// The account which changed the value, `None` for `AdminOrigin`.
+		pub who: Option<AccountId>,
+	}
+
+	type SomethingChangeOf<T> = SomethingChange<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency in which entry deposits are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The maximum length of a key, in bytes.
+		#[pallet::constant]
+		type MaxKeyLength: Get<u32>;
+
+		/// The maximum length of a value, in bytes.
+		#[pallet::constant]
+		type MaxValueLength: Get<u32>;
+
+		/// The maximum number of entries in a namespace, which bounds `clear_namespace`.
+		#[pallet::constant]
+		type MaxEntries: Get<u32>;
+
+		/// The deposit reserved per byte of key and value of an entry.
+		#[pallet::constant]
+		type ByteDeposit: Get<BalanceOf<Self>>;
+
+		/// The origin which may set `Something` to any value.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of changes of `Something` kept in `SomethingHistory`.
+		#[pallet::constant]
+		type HistoryDepth: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
 
-	// The pallet's runtime storage items.
-	// https://docs.substrate.io/v3/runtime/storage
+	/// The entries of each namespace, by owning account and key.
+	#[pallet::storage]
+	#[pallet::getter(fn entries)]
+	pub type Entries<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, KeyOf<T>, Entry<T>>;
+
+	/// The number of entries in each namespace.
+	#[pallet::storage]
+	#[pallet::getter(fn entry_count)]
+	pub type EntryCount<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// A counter shared across accounts.
 	#[pallet::storage]
 	#[pallet::getter(fn something)]
-	// Learn more about declaring storage items:
-	// https://docs.substrate.io/v3/runtime/storage#declaring-storage-items
-	pub type Something<T> = StorageValue<_, u32>;
+	pub type Something<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The latest `HistoryDepth` changes of `Something`, as a ring buffer of slots.
+	#[pallet::storage]
+	pub type SomethingHistory<T: Config> = StorageMap<_, Twox64Concat, u32, SomethingChangeOf<T>>;
+
+	/// The slot of `SomethingHistory` the next change is written to.
+	#[pallet::storage]
+	pub type SomethingHistoryHead<T> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		pub something: u32,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> GenesisConfig {
+			GenesisConfig { something: 0 }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			<Something<T>>::put(self.something);
+		}
+	}
 
-	// Pallets use events to inform users when important changes are made.
-	// https://docs.substrate.io/v3/runtime/events-and-errors
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Event documentation should end with an array that provides descriptive names for event
-		/// parameters. [something, who]
-		SomethingStored(u32, T::AccountId),
+		/// An entry was inserted or updated. [who, key, value]
+		EntrySet(T::AccountId, Vec<u8>, Vec<u8>),
+		/// An entry was removed. [who, key]
+		EntryRemoved(T::AccountId, Vec<u8>),
+		/// Every entry of a namespace was removed. [who, count]
+		NamespaceCleared(T::AccountId, u32),
+		/// `Something` was changed. [something, who]
+		SomethingStored(u32, Option<T::AccountId>),
 	}
 
-	// Errors inform users that something went wrong.
 	#[pallet::error]
 	pub enum Error<T> {
-		/// Error names should be descriptive.
-		NoneValue,
-		/// Errors should have helpful documentation associated with them.
+		/// A deposit or counter would overflow.
 		StorageOverflow,
+		/// The counter would drop below zero.
+		StorageUnderflow,
+		/// The key is longer than `MaxKeyLength`.
+		KeyTooLong,
+		/// The value is longer than `MaxValueLength`.
+		ValueTooLong,
+		/// The namespace already holds `MaxEntries` entries.
+		TooManyEntries,
+		/// There is no entry for the key in the namespace of the caller.
+		EntryNotFound,
+		/// The caller cannot afford the deposit of the entry.
+		InsufficientDeposit,
 	}
 
-	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
-	// These functions materialize as "extrinsics", which are often compared to transactions.
-	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
-			// Check that the extrinsic was signed and get the signer.
-			// This function will return an error if the extrinsic is not signed.
-			// https://docs.substrate.io/v3/runtime/origins
+		/// Insert or update the entry for `key` in the namespace of the caller, adjusting the
+		/// reserved deposit to the new size of the entry.
+		#[pallet::weight(T::WeightInfo::set(key.len() as u32, value.len() as u32))]
+		pub fn set(origin: OriginFor<T>, key: Vec<u8>, value: Vec<u8>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			let bounded_key: KeyOf<T> =
+				key.clone().try_into().map_err(|_| <Error<T>>::KeyTooLong)?;
+			let bounded_value: BoundedVec<u8, T::MaxValueLength> =
+				value.clone().try_into().map_err(|_| <Error<T>>::ValueTooLong)?;
+			let deposit = Self::deposit_for(key.len() + value.len())?;
 
-			// Update storage.
-			<Something<T>>::put(something);
+			match <Entries<T>>::get(&who, &bounded_key) {
+				Some(old) =>
+					if deposit > old.deposit {
+						T::Currency::reserve(&who, deposit - old.deposit)
+							.map_err(|_| <Error<T>>::InsufficientDeposit)?;
+					} else {
+						T::Currency::unreserve(&who, old.deposit - deposit);
+					},
+				None => {
+					let count = Self::entry_count(&who);
+					ensure!(count < T::MaxEntries::get(), <Error<T>>::TooManyEntries);
+					T::Currency::reserve(&who, deposit)
+						.map_err(|_| <Error<T>>::InsufficientDeposit)?;
+					<EntryCount<T>>::insert(&who, count + 1);
+				},
+			}
 
-			// Emit an event.
-			Self::deposit_event(Event::SomethingStored(something, who));
-			// Return a successful DispatchResultWithPostInfo
+			<Entries<T>>::insert(&who, &bounded_key, Entry { value: bounded_value, deposit });
+			Self::deposit_event(Event::EntrySet(who, key, value));
 			Ok(())
 		}
 
-		/// An example dispatchable that may throw a custom error.
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
-		pub fn cause_error(origin: OriginFor<T>) -> DispatchResult {
-			let _who = ensure_signed(origin)?;
-
-			// Read a value from storage.
-			match <Something<T>>::get() {
-				// Return an error if the value has not been set.
-				None => Err(Error::<T>::NoneValue)?,
-				Some(old) => {
-					// Increment the value read from storage; will error in the event of overflow.
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
-					// Update the value in storage with the incremented result.
-					<Something<T>>::put(new);
-					Ok(())
-				},
+		/// Remove the entry for `key` from the namespace of the caller and return its deposit.
+		#[pallet::weight(T::WeightInfo::remove(key.len() as u32))]
+		pub fn remove(origin: OriginFor<T>, key: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let bounded_key: KeyOf<T> =
+				key.clone().try_into().map_err(|_| <Error<T>>::KeyTooLong)?;
+			let entry =
+				<Entries<T>>::take(&who, &bounded_key).ok_or_else(|| <Error<T>>::EntryNotFound)?;
+
+			T::Currency::unreserve(&who, entry.deposit);
+			match Self::entry_count(&who).saturating_sub(1) {
+				0 => <EntryCount<T>>::remove(&who),
+				count => <EntryCount<T>>::insert(&who, count),
+			}
+			Self::deposit_event(Event::EntryRemoved(who, key));
+			Ok(())
+		}
+
+		/// Remove every entry from the namespace of the caller and return their deposits.
+		#[pallet::weight(T::WeightInfo::clear_namespace(T::MaxEntries::get()))]
+		pub fn clear_namespace(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut deposit = BalanceOf::<T>::zero();
+			let mut count = 0u32;
+			for (_, entry) in <Entries<T>>::drain_prefix(&who) {
+				deposit = deposit.saturating_add(entry.deposit);
+				count += 1;
 			}
+			<EntryCount<T>>::remove(&who);
+			T::Currency::unreserve(&who, deposit);
+
+			Self::deposit_event(Event::NamespaceCleared(who, count));
+			Ok(())
+		}
+
+		/// Set `Something` to `value`.
+		#[pallet::weight(T::WeightInfo::set_something())]
+		pub fn set_something(origin: OriginFor<T>, value: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::store_something(value, None);
+			Ok(())
+		}
+
+		/// Add one to `Something`.
+		#[pallet::weight(T::WeightInfo::increment())]
+		pub fn increment(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let value = Self::something().checked_add(1).ok_or(<Error<T>>::StorageOverflow)?;
+			Self::store_something(value, Some(who));
+			Ok(())
+		}
+
+		/// Subtract one from `Something`.
+		#[pallet::weight(T::WeightInfo::decrement())]
+		pub fn decrement(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let value = Self::something().checked_sub(1).ok_or(<Error<T>>::StorageUnderflow)?;
+			Self::store_something(value, Some(who));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The changes of `Something` kept in the history, oldest first.
+		pub fn something_history() -> Vec<SomethingChangeOf<T>> {
+			let depth = T::HistoryDepth::get();
+			let head = <SomethingHistoryHead<T>>::get();
+			(0..depth)
+				.filter_map(|i| <SomethingHistory<T>>::get(head.saturating_add(i) % depth))
+				.collect()
+		}
+
+		/// Store `value` into `Something` and record the change.
+		fn store_something(value: u32, who: Option<T::AccountId>) {
+			<Something<T>>::put(value);
+
+			let change = SomethingChange {
+				value,
+				block: <frame_system::Pallet<T>>::block_number(),
+				who: who.clone(),
+			};
+			let depth = T::HistoryDepth::get();
+			if depth > 0 {
+				let head = <SomethingHistoryHead<T>>::get() % depth;
+				<SomethingHistory<T>>::insert(head, change);
+				<SomethingHistoryHead<T>>::put((head + 1) % depth);
+			}
+
+			Self::deposit_event(Event::SomethingStored(value, who));
+		}
+
+		/// The deposit reserved for an entry of `len` bytes of key and value.
+		pub fn deposit_for(len: usize) -> Result<BalanceOf<T>, Error<T>> {
+			T::ByteDeposit::get()
+				.checked_mul(&(len as u32).into())
+				.ok_or(<Error<T>>::StorageOverflow)
 		}
 	}
 }
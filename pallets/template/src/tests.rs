@@ -1,20 +1,182 @@
 use crate::{mock::*, Error};
 use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+fn key(k: &[u8]) -> crate::KeyOf<Test> {
+	k.to_vec().try_into().unwrap()
+}
+
+#[test]
+fn set_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set(Origin::signed(1), b"k".to_vec(), b"value".to_vec()));
+		let entry = TemplateModule::entries(1, key(b"k")).unwrap();
+		assert_eq!(entry.value.into_inner(), b"value".to_vec());
+		// One byte of key and five bytes of value.
+		assert_eq!(entry.deposit, 12);
+		assert_eq!(Balances::reserved_balance(1), 12);
+		assert_eq!(TemplateModule::entry_count(1), 1);
+		System::assert_last_event(Event::TemplateModule(crate::Event::EntrySet(
+			1,
+			b"k".to_vec(),
+			b"value".to_vec(),
+		)));
+	});
+}
+
+#[test]
+fn set_should_adjust_deposit_of_existing_entry() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set(Origin::signed(1), b"k".to_vec(), b"value".to_vec()));
+		assert_ok!(TemplateModule::set(Origin::signed(1), b"k".to_vec(), b"longer".to_vec()));
+		assert_eq!(Balances::reserved_balance(1), 14);
+		assert_ok!(TemplateModule::set(Origin::signed(1), b"k".to_vec(), b"v".to_vec()));
+		assert_eq!(Balances::reserved_balance(1), 4);
+		assert_eq!(TemplateModule::entry_count(1), 1);
+	});
+}
+
+#[test]
+fn namespaces_should_be_per_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set(Origin::signed(1), b"k".to_vec(), b"one".to_vec()));
+		assert_ok!(TemplateModule::set(Origin::signed(2), b"k".to_vec(), b"two".to_vec()));
+		assert_eq!(TemplateModule::entries(1, key(b"k")).unwrap().value.into_inner(), b"one");
+		assert_eq!(TemplateModule::entries(2, key(b"k")).unwrap().value.into_inner(), b"two");
+	});
+}
+
+#[test]
+fn should_return_too_long_errors_when_set() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::set(Origin::signed(1), b"12345".to_vec(), b"v".to_vec()),
+			Error::<Test>::KeyTooLong
+		);
+		assert_noop!(
+			TemplateModule::set(Origin::signed(1), b"k".to_vec(), b"123456789".to_vec()),
+			Error::<Test>::ValueTooLong
+		);
+	});
+}
+
+#[test]
+fn should_return_too_many_entries_error_when_set() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set(Origin::signed(1), b"a".to_vec(), b"v".to_vec()));
+		assert_ok!(TemplateModule::set(Origin::signed(1), b"b".to_vec(), b"v".to_vec()));
+		assert_noop!(
+			TemplateModule::set(Origin::signed(1), b"c".to_vec(), b"v".to_vec()),
+			Error::<Test>::TooManyEntries
+		);
+		// Updating an existing entry is still allowed.
+		assert_ok!(TemplateModule::set(Origin::signed(1), b"a".to_vec(), b"w".to_vec()));
+	});
+}
+
+#[test]
+fn should_return_insufficient_deposit_error_when_set() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::set(Origin::signed(2), b"key".to_vec(), b"value".to_vec()),
+			Error::<Test>::InsufficientDeposit
+		);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn remove_should_work() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_ok!(TemplateModule::set(Origin::signed(1), b"k".to_vec(), b"value".to_vec()));
+		assert_ok!(TemplateModule::remove(Origin::signed(1), b"k".to_vec()));
+		assert_eq!(TemplateModule::entries(1, key(b"k")), None);
+		assert_eq!(TemplateModule::entry_count(1), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		System::assert_last_event(Event::TemplateModule(crate::Event::EntryRemoved(
+			1,
+			b"k".to_vec(),
+		)));
+		assert_noop!(
+			TemplateModule::remove(Origin::signed(1), b"k".to_vec()),
+			Error::<Test>::EntryNotFound
+		);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn clear_namespace_should_work() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		assert_ok!(TemplateModule::set(Origin::signed(1), b"a".to_vec(), b"v".to_vec()));
+		assert_ok!(TemplateModule::set(Origin::signed(1), b"b".to_vec(), b"v".to_vec()));
+		assert_ok!(TemplateModule::set(Origin::signed(2), b"a".to_vec(), b"v".to_vec()));
+		assert_ok!(TemplateModule::clear_namespace(Origin::signed(1)));
+		assert_eq!(TemplateModule::entry_count(1), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(TemplateModule::entries(1, key(b"a")), None);
+		assert_eq!(TemplateModule::entry_count(2), 1);
+		System::assert_last_event(Event::TemplateModule(crate::Event::NamespaceCleared(1, 2)));
+	});
+}
+
+#[test]
+fn genesis_config_should_set_something() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TemplateModule::something(), 5);
+	});
+}
+
+#[test]
+fn set_something_should_require_admin_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::set_something(Origin::signed(1), 42),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TemplateModule::set_something(Origin::root(), 42));
+		assert_eq!(TemplateModule::something(), 42);
+		System::assert_last_event(Event::TemplateModule(crate::Event::SomethingStored(42, None)));
+	});
+}
+
+#[test]
+fn increment_and_decrement_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::increment(Origin::signed(1)));
+		assert_eq!(TemplateModule::something(), 6);
+		System::assert_last_event(Event::TemplateModule(crate::Event::SomethingStored(6, Some(1))));
+		assert_ok!(TemplateModule::decrement(Origin::signed(2)));
+		assert_eq!(TemplateModule::something(), 5);
+	});
+}
+
+#[test]
+fn should_return_storage_overflow_error_when_increment() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_something(Origin::root(), u32::MAX));
+		assert_noop!(TemplateModule::increment(Origin::signed(1)), Error::<Test>::StorageOverflow);
+	});
+}
+
+#[test]
+fn should_return_storage_underflow_error_when_decrement() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_something(Origin::root(), 0));
+		assert_noop!(TemplateModule::decrement(Origin::signed(1)), Error::<Test>::StorageUnderflow);
+	});
+}
+
+#[test]
+fn something_history_should_keep_latest_changes() {
+	new_test_ext().execute_with(|| {
+		assert!(TemplateModule::something_history().is_empty());
+		for block in 1..=4 {
+			System::set_block_number(block);
+			assert_ok!(TemplateModule::increment(Origin::signed(block)));
+		}
+
+		let history = TemplateModule::something_history();
+		assert_eq!(history.iter().map(|change| change.value).collect::<Vec<_>>(), vec![7, 8, 9]);
+		assert_eq!(history.iter().map(|change| change.block).collect::<Vec<_>>(), vec![2, 3, 4]);
+		assert_eq!(history[0].who, Some(2));
 	});
 }
@@ -0,0 +1,117 @@
+//! Weights for pallet_template
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_template.
+pub trait WeightInfo {
+	fn set(k: u32, v: u32, ) -> Weight;
+	fn remove(k: u32, ) -> Weight;
+	fn clear_namespace(n: u32, ) -> Weight;
+	fn set_something() -> Weight;
+	fn increment() -> Weight;
+	fn decrement() -> Weight;
+}
+
+/// Weights for pallet_template using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: TemplateModule Entries (r:1 w:1)
+	// Storage: TemplateModule EntryCount (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn set(k: u32, v: u32, ) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(k as Weight))
+			.saturating_add((2_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: TemplateModule Entries (r:1 w:1)
+	// Storage: TemplateModule EntryCount (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn remove(k: u32, ) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(k as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: TemplateModule Entries (r:1 w:1)
+	// Storage: TemplateModule EntryCount (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear_namespace(n: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((12_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: TemplateModule SomethingHistoryHead (r:1 w:1)
+	// Storage: TemplateModule Something (r:0 w:1)
+	// Storage: TemplateModule SomethingHistory (r:0 w:1)
+	fn set_something() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule SomethingHistoryHead (r:1 w:1)
+	// Storage: TemplateModule SomethingHistory (r:0 w:1)
+	fn increment() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule SomethingHistoryHead (r:1 w:1)
+	// Storage: TemplateModule SomethingHistory (r:0 w:1)
+	fn decrement() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn set(k: u32, v: u32, ) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(k as Weight))
+			.saturating_add((2_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn remove(k: u32, ) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(k as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn clear_namespace(n: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((12_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_something() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn increment() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn decrement() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+}
@@ -0,0 +1,53 @@
+//! Benchmarking setup for pallet-tx-pause
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as TxPause;
+use frame_benchmarking::benchmarks;
+use frame_support::traits::EnsureOrigin;
+use sp_std::prelude::*;
+
+fn name<T: Config>(byte: u8) -> Vec<u8> {
+	vec![byte; T::MaxNameLen::get() as usize]
+}
+
+benchmarks! {
+	pause_pallet {
+		let origin = T::PauseOrigin::successful_origin();
+		let pallet = name::<T>(b'p');
+	}: _<T::Origin>(origin, pallet.clone())
+	verify {
+		assert!(TxPause::<T>::is_paused(&pallet, b"any"));
+	}
+
+	unpause_pallet {
+		let origin = T::PauseOrigin::successful_origin();
+		let pallet = name::<T>(b'p');
+		TxPause::<T>::pause_pallet(origin.clone(), pallet.clone())?;
+	}: _<T::Origin>(origin, pallet.clone())
+	verify {
+		assert!(!TxPause::<T>::is_paused(&pallet, b"any"));
+	}
+
+	pause_call {
+		let origin = T::PauseOrigin::successful_origin();
+		let pallet = name::<T>(b'p');
+		let call = name::<T>(b'c');
+	}: _<T::Origin>(origin, pallet.clone(), call.clone())
+	verify {
+		assert!(TxPause::<T>::is_paused(&pallet, &call));
+	}
+
+	unpause_call {
+		let origin = T::PauseOrigin::successful_origin();
+		let pallet = name::<T>(b'p');
+		let call = name::<T>(b'c');
+		TxPause::<T>::pause_call(origin.clone(), pallet.clone(), call.clone())?;
+	}: _<T::Origin>(origin, pallet.clone(), call.clone())
+	verify {
+		assert!(!TxPause::<T>::is_paused(&pallet, &call));
+	}
+
+	impl_benchmark_test_suite!(TxPause, crate::mock::new_test_ext(), crate::mock::Test);
+}
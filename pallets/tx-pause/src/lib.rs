@@ -0,0 +1,200 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Pauses whole pallets or single calls of the runtime by name, so that parts of the chain can
+/// be frozen during an incident without halting it.
+///
+/// The pallet implements `Contains<Call>` and is meant to be plugged in as the runtime's
+/// `BaseCallFilter`. Pallets listed in `UnpausablePallets` can never be paused.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use crate::WeightInfo;
+	use frame_support::{
+		dispatch::DispatchResult,
+		pallet_prelude::*,
+		traits::{CallMetadata, Contains, GetCallMetadata},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+
+	/// The name of a pallet, as in `construct_runtime!`.
+	pub type PalletNameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLen>;
+
+	/// The name of a call of a pallet.
+	pub type CallNameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLen>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may pause and unpause pallets and calls.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The pallets which can never be paused.
+		type UnpausablePallets: Contains<PalletNameOf<Self>>;
+
+		/// The maximum length of a pallet or call name, in bytes.
+		#[pallet::constant]
+		type MaxNameLen: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// The pallets of which every call is paused.
+	#[pallet::storage]
+	#[pallet::getter(fn paused_pallets)]
+	pub type PausedPallets<T: Config> = StorageMap<_, Blake2_128Concat, PalletNameOf<T>, ()>;
+
+	/// The single calls which are paused, by pallet and call name.
+	#[pallet::storage]
+	#[pallet::getter(fn paused_calls)]
+	pub type PausedCalls<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, PalletNameOf<T>, Blake2_128Concat, CallNameOf<T>, ()>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Every call of a pallet was paused. [pallet]
+		PalletPaused(Vec<u8>),
+		/// A pallet was unpaused. [pallet]
+		PalletUnpaused(Vec<u8>),
+		/// A call was paused. [pallet, call]
+		CallPaused(Vec<u8>, Vec<u8>),
+		/// A call was unpaused. [pallet, call]
+		CallUnpaused(Vec<u8>, Vec<u8>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The pallet or call name is longer than `MaxNameLen`.
+		NameTooLong,
+		/// The pallet is listed in `UnpausablePallets`.
+		Unpausable,
+		/// The pallet or call is already paused.
+		AlreadyPaused,
+		/// The pallet or call is not paused.
+		NotPaused,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pause every call of the pallet named `pallet`.
+		#[pallet::weight(T::WeightInfo::pause_pallet())]
+		pub fn pause_pallet(origin: OriginFor<T>, pallet: Vec<u8>) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			let pallet_name = Self::pausable_pallet_name(&pallet)?;
+			ensure!(!<PausedPallets<T>>::contains_key(&pallet_name), <Error<T>>::AlreadyPaused);
+
+			<PausedPallets<T>>::insert(&pallet_name, ());
+			Self::deposit_event(Event::PalletPaused(pallet));
+			Ok(())
+		}
+
+		/// Unpause the pallet named `pallet`. Calls paused one by one stay paused.
+		#[pallet::weight(T::WeightInfo::unpause_pallet())]
+		pub fn unpause_pallet(origin: OriginFor<T>, pallet: Vec<u8>) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			let pallet_name: PalletNameOf<T> =
+				pallet.clone().try_into().map_err(|_| <Error<T>>::NameTooLong)?;
+			<PausedPallets<T>>::take(&pallet_name).ok_or(<Error<T>>::NotPaused)?;
+
+			Self::deposit_event(Event::PalletUnpaused(pallet));
+			Ok(())
+		}
+
+		/// Pause the call named `call` of the pallet named `pallet`.
+		#[pallet::weight(T::WeightInfo::pause_call())]
+		pub fn pause_call(origin: OriginFor<T>, pallet: Vec<u8>, call: Vec<u8>) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			let pallet_name = Self::pausable_pallet_name(&pallet)?;
+			let call_name: CallNameOf<T> =
+				call.clone().try_into().map_err(|_| <Error<T>>::NameTooLong)?;
+			ensure!(
+				!<PausedCalls<T>>::contains_key(&pallet_name, &call_name),
+				<Error<T>>::AlreadyPaused
+			);
+
+			<PausedCalls<T>>::insert(&pallet_name, &call_name, ());
+			Self::deposit_event(Event::CallPaused(pallet, call));
+			Ok(())
+		}
+
+		/// Unpause the call named `call` of the pallet named `pallet`.
+		#[pallet::weight(T::WeightInfo::unpause_call())]
+		pub fn unpause_call(
+			origin: OriginFor<T>,
+			pallet: Vec<u8>,
+			call: Vec<u8>,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			let pallet_name: PalletNameOf<T> =
+				pallet.clone().try_into().map_err(|_| <Error<T>>::NameTooLong)?;
+			let call_name: CallNameOf<T> =
+				call.clone().try_into().map_err(|_| <Error<T>>::NameTooLong)?;
+			<PausedCalls<T>>::take(&pallet_name, &call_name).ok_or(<Error<T>>::NotPaused)?;
+
+			Self::deposit_event(Event::CallUnpaused(pallet, call));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether the call named `call` of the pallet named `pallet` is paused, either on its
+		/// own or along with its whole pallet.
+		pub fn is_paused(pallet: &[u8], call: &[u8]) -> bool {
+			let pallet_name: PalletNameOf<T> = match pallet.to_vec().try_into() {
+				Ok(name) => name,
+				// Names too long to be stored cannot have been paused.
+				Err(_) => return false,
+			};
+			if T::UnpausablePallets::contains(&pallet_name) {
+				return false
+			}
+			if <PausedPallets<T>>::contains_key(&pallet_name) {
+				return true
+			}
+			match CallNameOf::<T>::try_from(call.to_vec()) {
+				Ok(call_name) => <PausedCalls<T>>::contains_key(&pallet_name, &call_name),
+				Err(_) => false,
+			}
+		}
+
+		/// Bound `pallet` and check it may be paused.
+		fn pausable_pallet_name(pallet: &[u8]) -> Result<PalletNameOf<T>, Error<T>> {
+			let pallet_name: PalletNameOf<T> =
+				pallet.to_vec().try_into().map_err(|_| <Error<T>>::NameTooLong)?;
+			ensure!(!T::UnpausablePallets::contains(&pallet_name), <Error<T>>::Unpausable);
+			Ok(pallet_name)
+		}
+	}
+
+	/// Lets through every call which is not paused.
+	impl<T: Config> Contains<<T as frame_system::Config>::Call> for Pallet<T>
+	where
+		<T as frame_system::Config>::Call: GetCallMetadata,
+	{
+		fn contains(call: &<T as frame_system::Config>::Call) -> bool {
+			let CallMetadata { pallet_name, function_name } = call.get_call_metadata();
+			!Self::is_paused(pallet_name.as_bytes(), function_name.as_bytes())
+		}
+	}
+}
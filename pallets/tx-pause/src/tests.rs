@@ -0,0 +1,112 @@
+use crate::{mock::*, Error};
+use frame_support::{assert_noop, assert_ok, dispatch::Dispatchable, traits::Contains};
+use sp_runtime::DispatchError;
+
+fn transfer() -> Call {
+	Call::Balances(pallet_balances::Call::transfer { dest: 2, value: 5 })
+}
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark { remark: vec![] })
+}
+
+#[test]
+fn pause_pallet_should_filter_its_calls() {
+	new_test_ext().execute_with(|| {
+		assert!(TxPause::contains(&transfer()));
+		assert_ok!(TxPause::pause_pallet(Origin::root(), b"Balances".to_vec()));
+		assert!(TxPause::is_paused(b"Balances", b"transfer"));
+		assert!(!TxPause::contains(&transfer()));
+		assert!(transfer().dispatch(Origin::signed(1)).is_err());
+		assert_eq!(Balances::free_balance(2), 10);
+		System::assert_last_event(Event::TxPause(crate::Event::PalletPaused(b"Balances".to_vec())));
+	});
+}
+
+#[test]
+fn unpause_pallet_should_let_its_calls_through() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause_pallet(Origin::root(), b"Balances".to_vec()));
+		assert_ok!(TxPause::unpause_pallet(Origin::root(), b"Balances".to_vec()));
+		assert!(TxPause::contains(&transfer()));
+		assert_ok!(transfer().dispatch(Origin::signed(1)));
+		assert_eq!(Balances::free_balance(2), 15);
+		System::assert_last_event(Event::Balances(pallet_balances::Event::Transfer(1, 2, 5)));
+		assert_noop!(
+			TxPause::unpause_pallet(Origin::root(), b"Balances".to_vec()),
+			Error::<Test>::NotPaused
+		);
+	});
+}
+
+#[test]
+fn pause_call_should_filter_only_that_call() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause_call(Origin::root(), b"Balances".to_vec(), b"transfer".to_vec()));
+		assert!(!TxPause::contains(&transfer()));
+		assert!(!TxPause::is_paused(b"Balances", b"transfer_keep_alive"));
+		System::assert_last_event(Event::TxPause(crate::Event::CallPaused(
+			b"Balances".to_vec(),
+			b"transfer".to_vec(),
+		)));
+
+		assert_ok!(TxPause::unpause_call(
+			Origin::root(),
+			b"Balances".to_vec(),
+			b"transfer".to_vec()
+		));
+		assert!(TxPause::contains(&transfer()));
+		System::assert_last_event(Event::TxPause(crate::Event::CallUnpaused(
+			b"Balances".to_vec(),
+			b"transfer".to_vec(),
+		)));
+	});
+}
+
+#[test]
+fn should_require_pause_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TxPause::pause_pallet(Origin::signed(1), b"Balances".to_vec()),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TxPause::pause_call(Origin::signed(1), b"Balances".to_vec(), b"transfer".to_vec()),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn unpausable_pallets_should_never_be_paused() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TxPause::pause_pallet(Origin::root(), b"System".to_vec()),
+			Error::<Test>::Unpausable
+		);
+		assert_noop!(
+			TxPause::pause_call(Origin::root(), b"System".to_vec(), b"remark".to_vec()),
+			Error::<Test>::Unpausable
+		);
+		assert!(TxPause::contains(&remark()));
+	});
+}
+
+#[test]
+fn should_return_errors_for_repeated_or_long_names() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause_pallet(Origin::root(), b"Balances".to_vec()));
+		assert_noop!(
+			TxPause::pause_pallet(Origin::root(), b"Balances".to_vec()),
+			Error::<Test>::AlreadyPaused
+		);
+		assert_noop!(
+			TxPause::pause_pallet(Origin::root(), vec![b'a'; 17]),
+			Error::<Test>::NameTooLong
+		);
+		assert_noop!(
+			TxPause::unpause_call(Origin::root(), b"Balances".to_vec(), b"transfer".to_vec()),
+			Error::<Test>::NotPaused
+		);
+	});
+}
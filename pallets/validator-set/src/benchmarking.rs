@@ -0,0 +1,42 @@
+//! Benchmarking setup for pallet-validator-set
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as ValidatorSet;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::{EnsureOrigin, Get};
+use sp_runtime::traits::Convert;
+use sp_std::prelude::*;
+
+fn validator<T: Config>(index: u32) -> T::ValidatorId {
+	T::ValidatorIdOf::convert(account("validator", index, 0)).unwrap()
+}
+
+fn fill_validators<T: Config>(count: u32) {
+	<Validators<T>>::put((0..count).map(validator::<T>).collect::<Vec<_>>());
+}
+
+benchmarks! {
+	add_validator {
+		let origin = T::AddRemoveOrigin::successful_origin();
+		let max = T::MaxValidators::get();
+		fill_validators::<T>(max - 1);
+		let new = validator::<T>(max);
+	}: _<<T as frame_system::Config>::Origin>(origin, new.clone())
+	verify {
+		assert!(ValidatorSet::<T>::validators().contains(&new));
+	}
+
+	remove_validator {
+		let origin = T::AddRemoveOrigin::successful_origin();
+		let max = T::MaxValidators::get();
+		fill_validators::<T>(max);
+		let old = validator::<T>(max - 1);
+	}: _<<T as frame_system::Config>::Origin>(origin, old.clone())
+	verify {
+		assert!(!ValidatorSet::<T>::validators().contains(&old));
+	}
+
+	impl_benchmark_test_suite!(ValidatorSet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,187 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Manages the validators of `pallet_session`. An origin, root in the runtime, adds and
+/// removes validators; the new set is handed to `pallet_session` at the next session rotation
+/// and becomes active one session later, for the validators which have set their session keys.
+///
+/// The pallet is meant to be plugged in as the `SessionManager` of `pallet_session`, wrapped
+/// in `pallet_session::historical::NoteHistoricalRoot` when key ownership proofs are needed.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use crate::WeightInfo;
+	use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
+	use frame_system::pallet_prelude::*;
+	use sp_staking::SessionIndex;
+	use sp_std::prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_session::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may add and remove validators.
+		type AddRemoveOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
+
+		/// The minimum number of validators, so that the chain keeps producing blocks.
+		#[pallet::constant]
+		type MinValidators: Get<u32>;
+
+		/// The maximum number of validators.
+		#[pallet::constant]
+		type MaxValidators: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// The validators handed to `pallet_session` at the next rotation after a change.
+	#[pallet::storage]
+	#[pallet::getter(fn validators)]
+	pub type Validators<T: Config> = StorageValue<_, Vec<T::ValidatorId>, ValueQuery>;
+
+	/// Whether `Validators` changed since it was last handed to `pallet_session`.
+	#[pallet::storage]
+	pub type ValidatorsChanged<T> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub initial_validators: Vec<T::ValidatorId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { initial_validators: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			assert!(
+				self.initial_validators.len() as u32 <= T::MaxValidators::get(),
+				"Too many initial validators"
+			);
+			<Validators<T>>::put(&self.initial_validators);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A validator was added, it validates from the session after next. [validator]
+		ValidatorAdded(T::ValidatorId),
+		/// A validator was removed, it stops validating from the session after next. [validator]
+		ValidatorRemoved(T::ValidatorId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is already a validator.
+		AlreadyValidator,
+		/// The account is not a validator.
+		NotValidator,
+		/// Removing the validator would leave less than `MinValidators`.
+		TooFewValidators,
+		/// Adding the validator would make more than `MaxValidators`.
+		TooManyValidators,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add `validator` to the validators. It has to set its session keys to validate.
+		#[pallet::weight(<T as Config>::WeightInfo::add_validator())]
+		pub fn add_validator(origin: OriginFor<T>, validator: T::ValidatorId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+			let mut validators = Self::validators();
+			ensure!(!validators.contains(&validator), <Error<T>>::AlreadyValidator);
+			ensure!(
+				(validators.len() as u32) < T::MaxValidators::get(),
+				<Error<T>>::TooManyValidators
+			);
+
+			validators.push(validator.clone());
+			Self::put_validators(validators);
+			Self::deposit_event(Event::ValidatorAdded(validator));
+			Ok(())
+		}
+
+		/// Remove `validator` from the validators.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_validator())]
+		pub fn remove_validator(origin: OriginFor<T>, validator: T::ValidatorId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+			let mut validators = Self::validators();
+			let index = validators
+				.iter()
+				.position(|v| v == &validator)
+				.ok_or(<Error<T>>::NotValidator)?;
+			ensure!(
+				validators.len() as u32 > T::MinValidators::get(),
+				<Error<T>>::TooFewValidators
+			);
+
+			validators.remove(index);
+			Self::put_validators(validators);
+			Self::deposit_event(Event::ValidatorRemoved(validator));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn put_validators(validators: Vec<T::ValidatorId>) {
+			<Validators<T>>::put(validators);
+			<ValidatorsChanged<T>>::put(true);
+		}
+	}
+
+	impl<T: Config> pallet_session::SessionManager<T::ValidatorId> for Pallet<T> {
+		fn new_session(_: SessionIndex) -> Option<Vec<T::ValidatorId>> {
+			if <ValidatorsChanged<T>>::take() {
+				Some(Self::validators())
+			} else {
+				None
+			}
+		}
+
+		fn new_session_genesis(_: SessionIndex) -> Option<Vec<T::ValidatorId>> {
+			// Without genesis validators `pallet_session` falls back to its genesis keys.
+			Some(Self::validators()).filter(|validators| !validators.is_empty())
+		}
+
+		fn end_session(_: SessionIndex) {}
+		fn start_session(_: SessionIndex) {}
+	}
+
+	impl<T: Config> pallet_session::historical::SessionManager<T::ValidatorId, ()> for Pallet<T> {
+		fn new_session(new_index: SessionIndex) -> Option<Vec<(T::ValidatorId, ())>> {
+			<Self as pallet_session::SessionManager<_>>::new_session(new_index)
+				.map(|validators| validators.into_iter().map(|v| (v, ())).collect())
+		}
+
+		fn new_session_genesis(new_index: SessionIndex) -> Option<Vec<(T::ValidatorId, ())>> {
+			<Self as pallet_session::SessionManager<_>>::new_session_genesis(new_index)
+				.map(|validators| validators.into_iter().map(|v| (v, ())).collect())
+		}
+
+		fn end_session(_: SessionIndex) {}
+		fn start_session(_: SessionIndex) {}
+	}
+}
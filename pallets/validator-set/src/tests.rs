@@ -0,0 +1,86 @@
+use crate::{mock::*, Error};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{testing::UintAuthorityId, DispatchError};
+
+#[test]
+fn genesis_config_should_set_validators() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ValidatorSet::validators(), vec![1, 2, 3]);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+	});
+}
+
+#[test]
+fn add_validator_should_take_effect_the_session_after_next() {
+	new_test_ext().execute_with(|| {
+		System::inc_providers(&4);
+		assert_ok!(Session::set_keys(Origin::signed(4), UintAuthorityId(4), vec![]));
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_eq!(ValidatorSet::validators(), vec![1, 2, 3, 4]);
+		System::assert_last_event(Event::ValidatorSet(crate::Event::ValidatorAdded(4)));
+
+		rotate_sessions(1);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+		rotate_sessions(1);
+		assert_eq!(Session::validators(), vec![1, 2, 3, 4]);
+	});
+}
+
+#[test]
+fn added_validator_without_keys_should_not_validate() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		rotate_sessions(2);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+	});
+}
+
+#[test]
+fn remove_validator_should_take_effect_the_session_after_next() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 2));
+		assert_eq!(ValidatorSet::validators(), vec![1, 3]);
+		System::assert_last_event(Event::ValidatorSet(crate::Event::ValidatorRemoved(2)));
+
+		rotate_sessions(2);
+		assert_eq!(Session::validators(), vec![1, 3]);
+	});
+}
+
+#[test]
+fn should_require_add_remove_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ValidatorSet::add_validator(Origin::signed(1), 4), DispatchError::BadOrigin);
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::signed(1), 3),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn should_return_errors_when_adding_or_removing() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 3),
+			Error::<Test>::AlreadyValidator
+		);
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 4),
+			Error::<Test>::NotValidator
+		);
+
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 5),
+			Error::<Test>::TooManyValidators
+		);
+
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 4));
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 3));
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 2),
+			Error::<Test>::TooFewValidators
+		);
+	});
+}
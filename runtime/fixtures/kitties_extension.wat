@@ -0,0 +1,34 @@
+;; Calls a function of the kitties chain extension and returns its output. The input of the
+;; contract is the id of the function, a little endian `u32`, followed by the function's input.
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32))
+	)
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) length of the input
+	;; [4, 8) function id
+	;; [8, 1024) input of the function
+	;; [1024, 1028) length of the output
+	;; [1028, 65536) output of the function
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(i32.store (i32.const 0) (i32.const 1020))
+		(call $seal_input (i32.const 4) (i32.const 0))
+		(i32.store (i32.const 1024) (i32.const 64508))
+		(drop
+			(call $seal_call_chain_extension
+				(i32.load (i32.const 4))
+				(i32.const 8)
+				(i32.sub (i32.load (i32.const 0)) (i32.const 4))
+				(i32.const 1028)
+				(i32.const 1024)
+			)
+		)
+		(call $seal_return (i32.const 0) (i32.const 1028) (i32.load (i32.const 1024)))
+	)
+)
@@ -0,0 +1,341 @@
+//! Some configurable implementations as associated type for the runtime.
+
+use crate::{
+	AccountId, Authorship, Balance, Balances, Call, Hash, Identity, Kitties, MaxAuthorities,
+	Origin, Runtime, Session, Staking, Treasury, ValidatorSet, MICROUNIT, MILLIUNIT,
+};
+use codec::Encode;
+use frame_support::{
+	traits::{Contains, Currency, EnsureOrigin, FindAuthor, Get, Imbalance, OnUnbalanced},
+	weights::{
+		constants::{ExtrinsicBaseWeight, WEIGHT_PER_SECOND},
+		Weight, WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial,
+	},
+};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use pallet_evm::{AddressMapping, FeeCalculator, GasWeightMapping};
+use pallet_identity::{Data, Judgement};
+use pallet_session::{historical, SessionManager};
+use pallet_staking::{Exposure, StashOf};
+use smallvec::smallvec;
+use sp_core::{H160, U256};
+use sp_runtime::{
+	traits::{Convert, Dispatchable},
+	ConsensusEngineId, DispatchError, DispatchResult, Perbill,
+};
+use sp_staking::SessionIndex;
+use sp_std::prelude::*;
+
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// Credits the author of the current block.
+pub struct Author;
+
+impl OnUnbalanced<NegativeImbalance> for Author {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		if let Some(author) = Authorship::author() {
+			Balances::resolve_creating(&author, amount);
+		}
+	}
+}
+
+/// Splits transaction fees 80/20 between the treasury and the block author, and gives tips to
+/// the block author.
+pub struct DealWithFees;
+
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
+		if let Some(fees) = fees_then_tips.next() {
+			let (to_treasury, mut to_author) = fees.ration(80, 20);
+			if let Some(tips) = fees_then_tips.next() {
+				tips.merge_into(&mut to_author);
+			}
+			Treasury::on_unbalanced(to_treasury);
+			Author::on_unbalanced(to_author);
+		}
+	}
+}
+
+/// Converts weight to fee linearly, so that the base weight of an extrinsic costs a tenth of a
+/// `MILLIUNIT` before the fee multiplier applies.
+pub struct WeightToFee;
+
+impl WeightToFeePolynomial for WeightToFee {
+	type Balance = Balance;
+
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		let p = MILLIUNIT / 10;
+		let q = Balance::from(ExtrinsicBaseWeight::get());
+		smallvec![WeightToFeeCoefficient {
+			degree: 1,
+			negative: false,
+			coeff_frac: Perbill::from_rational(p % q, q),
+			coeff_integer: p / q,
+		}]
+	}
+}
+
+/// Names the owners of proofs by the display name of their identity, once a registrar judged it
+/// reasonable or known good.
+pub struct JudgedIdentity;
+
+impl pallet_poe::IdentityProvider<AccountId> for JudgedIdentity {
+	fn display_name(who: &AccountId) -> Option<Vec<u8>> {
+		let registration = Identity::identity(who)?;
+		let judged = registration.judgements.iter().any(|(_, judgement)| {
+			matches!(judgement, Judgement::Reasonable | Judgement::KnownGood)
+		});
+		match registration.info.display {
+			Data::Raw(name) if judged => Some(name.to_vec()),
+			_ => None,
+		}
+	}
+}
+
+/// Hands `pallet_session` the validators `ValidatorSet` holds, followed by those `Staking`
+/// elects, up to `MaxAuthorities`. Validators of `ValidatorSet` without a bond validate with an
+/// empty exposure: they author blocks and can be reported, but have nothing to be slashed or paid.
+pub struct StakingAndValidatorSet;
+
+type FullIdentification = Exposure<AccountId, Balance>;
+
+impl StakingAndValidatorSet {
+	/// The validators `Staking` elected for the era being planned, when no new era is.
+	fn elected() -> Vec<(AccountId, FullIdentification)> {
+		Staking::current_era()
+			.map(|era| pallet_staking::ErasStakers::<Runtime>::iter_prefix(era).collect())
+			.unwrap_or_default()
+	}
+
+	fn merge(
+		permissioned: Vec<AccountId>,
+		elected: Vec<(AccountId, FullIdentification)>,
+	) -> Vec<(AccountId, FullIdentification)> {
+		let mut validators: Vec<(AccountId, FullIdentification)> = permissioned
+			.into_iter()
+			.map(|v| {
+				let exposure = elected
+					.iter()
+					.find(|(elected, _)| elected == &v)
+					.map(|(_, exposure)| exposure.clone())
+					.unwrap_or_default();
+				(v, exposure)
+			})
+			.collect();
+		for (v, exposure) in elected {
+			if !validators.iter().any(|(validator, _)| validator == &v) {
+				validators.push((v, exposure));
+			}
+		}
+		validators.truncate(MaxAuthorities::get() as usize);
+		validators
+	}
+}
+
+impl historical::SessionManager<AccountId, FullIdentification> for StakingAndValidatorSet {
+	fn new_session(new_index: SessionIndex) -> Option<Vec<(AccountId, FullIdentification)>> {
+		// Both are asked every session, so that `Staking` plans its eras.
+		let elected =
+			<Staking as historical::SessionManager<_, FullIdentification>>::new_session(new_index);
+		let permissioned = <ValidatorSet as SessionManager<_>>::new_session(new_index);
+		if elected.is_none() && permissioned.is_none() {
+			return None
+		}
+		Some(Self::merge(
+			permissioned.unwrap_or_else(ValidatorSet::validators),
+			elected.unwrap_or_else(Self::elected),
+		))
+	}
+
+	fn new_session_genesis(
+		new_index: SessionIndex,
+	) -> Option<Vec<(AccountId, FullIdentification)>> {
+		let elected =
+			<Staking as historical::SessionManager<_, FullIdentification>>::new_session_genesis(
+				new_index,
+			);
+		let permissioned = <ValidatorSet as SessionManager<_>>::new_session_genesis(new_index);
+		if elected.is_none() && permissioned.is_none() {
+			return None
+		}
+		Some(Self::merge(permissioned.unwrap_or_default(), elected.unwrap_or_default()))
+	}
+
+	fn end_session(end_index: SessionIndex) {
+		<Staking as historical::SessionManager<_, FullIdentification>>::end_session(end_index);
+		<ValidatorSet as SessionManager<_>>::end_session(end_index);
+	}
+
+	fn start_session(start_index: SessionIndex) {
+		<Staking as historical::SessionManager<_, FullIdentification>>::start_session(start_index);
+		<ValidatorSet as SessionManager<_>>::start_session(start_index);
+	}
+}
+
+/// Identifies validators by their stash account, or by their own account for validators of
+/// `ValidatorSet` which do not bond, so that those can set their session keys too.
+pub struct StashOrValidatorSet;
+
+impl Convert<AccountId, Option<AccountId>> for StashOrValidatorSet {
+	fn convert(who: AccountId) -> Option<AccountId> {
+		StashOf::<Runtime>::convert(who.clone())
+			.or_else(|| ValidatorSet::validators().contains(&who).then(|| who))
+	}
+}
+
+/// Admits signed origins of the controllers of the current validators, which the validators
+/// also sign anchored batches of proofs with.
+pub struct EnsureValidatorController;
+
+impl EnsureValidatorController {
+	fn is_validator_controller(who: &AccountId) -> bool {
+		Staking::ledger(who).map_or(false, |ledger| Session::validators().contains(&ledger.stash))
+	}
+}
+
+impl EnsureOrigin<Origin> for EnsureValidatorController {
+	type Success = AccountId;
+
+	fn try_origin(o: Origin) -> Result<AccountId, Origin> {
+		o.into().and_then(|o| match o {
+			frame_system::RawOrigin::Signed(who) if Self::is_validator_controller(&who) => Ok(who),
+			r => Err(Origin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		let controller = Session::validators()
+			.iter()
+			.find_map(|stash| Staking::bonded(stash))
+			.expect("benchmarks run on a chain with validators");
+		Origin::from(frame_system::RawOrigin::Signed(controller))
+	}
+}
+
+/// The pallets `TxPause` may never pause: the inherents which keep the chain producing blocks,
+/// and the calls root and the council need to unpause everything else, including the multisig,
+/// batch and proxy calls through which their members may act.
+pub struct UnpausablePallets;
+
+impl Contains<pallet_tx_pause::PalletNameOf<Runtime>> for UnpausablePallets {
+	fn contains(pallet: &pallet_tx_pause::PalletNameOf<Runtime>) -> bool {
+		matches!(
+			&pallet[..],
+			b"System" |
+				b"Timestamp" | b"Sudo" |
+				b"Council" | b"Democracy" |
+				b"TxPause" | b"Multisig" |
+				b"Utility" | b"Proxy"
+		)
+	}
+}
+
+/// Exposes `Kitties` to smart contracts. A function takes its SCALE encoded input and returns a
+/// SCALE encoded output:
+///
+/// - `1`: the owner of `kitty_id: Hash`, as `Option<AccountId>`.
+/// - `2`: the kitties of `owner: AccountId`, as `Vec<Hash>`.
+/// - `3`: transfers `(to: AccountId, kitty_id: Hash)`, a kitty owned by the calling contract, as
+///   `Result<(), DispatchError>`. The transfer is dispatched as `Kitties::transfer`, so it fails
+///   while the call is paused.
+pub struct KittiesExtension;
+
+impl ChainExtension<Runtime> for KittiesExtension {
+	fn call<E>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Runtime>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let mut env = env.buf_in_buf_out();
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		match func_id {
+			1 => {
+				env.charge_weight(db_weight.reads(1))?;
+				let kitty_id: Hash = env.read_as()?;
+				let owner = Kitties::kitties(&kitty_id).map(|kitty| kitty.owner);
+				env.write(&owner.encode(), false, None)?;
+			},
+			2 => {
+				env.charge_weight(db_weight.reads(1))?;
+				let owner: AccountId = env.read_as()?;
+				let owned = Kitties::kittes_owned(&owner).into_inner();
+				env.write(&owned.encode(), false, None)?;
+			},
+			3 => {
+				env.charge_weight(db_weight.reads_writes(3, 3))?;
+				let (to, kitty_id): (AccountId, Hash) = env.read_as()?;
+				let from = env.ext().address().clone();
+				let result = dispatch_kitty_transfer(&from, to, kitty_id);
+				env.write(&result.encode(), false, None)?;
+			},
+			_ => return Err(DispatchError::Other("Unknown kitties extension function")),
+		}
+		Ok(RetVal::Converging(0))
+	}
+}
+
+/// Transfers a kitty of `owner` by dispatching `Kitties::transfer` as them, so transfers made by
+/// contracts go through the same checks as extrinsics, including the `TxPause` call filter.
+pub fn dispatch_kitty_transfer(owner: &AccountId, to: AccountId, kitty_id: Hash) -> DispatchResult {
+	let call = Call::Kitties(pallet_kitties::Call::transfer { to, kitty_id });
+	call.dispatch(Origin::signed(owner.clone())).map(|_| ()).map_err(|e| e.error)
+}
+
+/// Maps an EVM address to the account whose first 20 bytes are the address, followed by zeros,
+/// and an account to an address by truncating it to 20 bytes, like `EnsureAddressTruncated`.
+pub struct TruncatedAddressMapping;
+
+impl TruncatedAddressMapping {
+	pub fn address_of(account: &AccountId) -> H160 {
+		H160::from_slice(&AsRef::<[u8; 32]>::as_ref(account)[0..20])
+	}
+}
+
+impl AddressMapping<AccountId> for TruncatedAddressMapping {
+	fn into_account_id(address: H160) -> AccountId {
+		let mut account = [0u8; 32];
+		account[0..20].copy_from_slice(address.as_bytes());
+		AccountId::from(account)
+	}
+}
+
+/// Finds the author of the block, as the address of its account.
+pub struct FindAuthorTruncated;
+
+impl FindAuthor<H160> for FindAuthorTruncated {
+	fn find_author<'a, I>(digests: I) -> Option<H160>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		<Runtime as pallet_authorship::Config>::FindAuthor::find_author(digests)
+			.map(|author| TruncatedAddressMapping::address_of(&author))
+	}
+}
+
+/// The weight of a unit of gas: the EVM executes about 40 million gas per second.
+pub const WEIGHT_PER_GAS: Weight = WEIGHT_PER_SECOND / 40_000_000;
+
+/// Converts gas to weight at `WEIGHT_PER_GAS`.
+pub struct FixedGasWeightMapping;
+
+impl GasWeightMapping for FixedGasWeightMapping {
+	fn gas_to_weight(gas: u64) -> Weight {
+		gas.saturating_mul(WEIGHT_PER_GAS)
+	}
+
+	fn weight_to_gas(weight: Weight) -> u64 {
+		weight / WEIGHT_PER_GAS
+	}
+}
+
+/// Prices gas so a plain transfer of 21_000 gas costs about as much as a base extrinsic.
+pub struct FixedGasPrice;
+
+impl FeeCalculator for FixedGasPrice {
+	fn min_gas_price() -> U256 {
+		U256::from(MICROUNIT / 200)
+	}
+}
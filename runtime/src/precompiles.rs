@@ -0,0 +1,302 @@
+//! The precompiled contracts of the EVM, including `Kitties` exposed as an ERC-721 token.
+
+use crate::{
+	impls::{dispatch_kitty_transfer, TruncatedAddressMapping},
+	Kitties, Runtime,
+};
+use fp_evm::{
+	Context, ExitError, ExitSucceed, Log, Precompile, PrecompileFailure, PrecompileOutput,
+	PrecompileResult, PrecompileSet,
+};
+use frame_support::{traits::Get, weights::constants::RocksDbWeight};
+use pallet_evm::{runner::stack, AddressMapping, CallInfo, CreateInfo, GasWeightMapping, Runner};
+use pallet_evm_precompile_simple::{ECRecover, Identity, Ripemd160, Sha256};
+use sp_core::{H160, H256, U256};
+use sp_std::prelude::*;
+
+environmental::environmental!(TRANSACTION_ORIGIN: H160);
+
+/// Run `f` in an EVM transaction sent by `origin`, which precompiles get by `transaction_origin`.
+pub fn with_transaction_origin<R>(mut origin: H160, f: impl FnOnce() -> R) -> R {
+	TRANSACTION_ORIGIN::using(&mut origin, f)
+}
+
+/// The sender of the EVM transaction being run, if any.
+pub fn transaction_origin() -> Option<H160> {
+	TRANSACTION_ORIGIN::with(|origin| *origin)
+}
+
+/// Runs EVM transactions with the stack runner of `pallet_evm`, recording their sender for
+/// `transaction_origin`: the `Context` of precompiles only names their immediate caller.
+pub struct OriginRecordingRunner;
+
+impl Runner<Runtime> for OriginRecordingRunner {
+	type Error = <stack::Runner<Runtime> as Runner<Runtime>>::Error;
+
+	fn call(
+		source: H160,
+		target: H160,
+		input: Vec<u8>,
+		value: U256,
+		gas_limit: u64,
+		max_fee_per_gas: Option<U256>,
+		max_priority_fee_per_gas: Option<U256>,
+		nonce: Option<U256>,
+		access_list: Vec<(H160, Vec<H256>)>,
+		config: &evm::Config,
+	) -> Result<CallInfo, Self::Error> {
+		with_transaction_origin(source, || {
+			stack::Runner::<Runtime>::call(
+				source,
+				target,
+				input,
+				value,
+				gas_limit,
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				access_list,
+				config,
+			)
+		})
+	}
+
+	fn create(
+		source: H160,
+		init: Vec<u8>,
+		value: U256,
+		gas_limit: u64,
+		max_fee_per_gas: Option<U256>,
+		max_priority_fee_per_gas: Option<U256>,
+		nonce: Option<U256>,
+		access_list: Vec<(H160, Vec<H256>)>,
+		config: &evm::Config,
+	) -> Result<CreateInfo, Self::Error> {
+		with_transaction_origin(source, || {
+			stack::Runner::<Runtime>::create(
+				source,
+				init,
+				value,
+				gas_limit,
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				access_list,
+				config,
+			)
+		})
+	}
+
+	fn create2(
+		source: H160,
+		init: Vec<u8>,
+		salt: H256,
+		value: U256,
+		gas_limit: u64,
+		max_fee_per_gas: Option<U256>,
+		max_priority_fee_per_gas: Option<U256>,
+		nonce: Option<U256>,
+		access_list: Vec<(H160, Vec<H256>)>,
+		config: &evm::Config,
+	) -> Result<CreateInfo, Self::Error> {
+		with_transaction_origin(source, || {
+			stack::Runner::<Runtime>::create2(
+				source,
+				init,
+				salt,
+				value,
+				gas_limit,
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				access_list,
+				config,
+			)
+		})
+	}
+}
+
+/// The address of the precompile with the given index.
+pub const fn precompile_address(index: u64) -> H160 {
+	let bytes = index.to_be_bytes();
+	H160([
+		0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, bytes[0], bytes[1], bytes[2], bytes[3], bytes[4],
+		bytes[5], bytes[6], bytes[7],
+	])
+}
+
+/// The address of the ERC-721 contract of kitties.
+pub const KITTIES_ERC721_ADDRESS: H160 = precompile_address(1024);
+
+/// The precompiles of the runtime: the Ethereum ones at 1 to 4, and the kitties at 1024.
+#[derive(Default)]
+pub struct RuntimePrecompiles;
+
+impl RuntimePrecompiles {
+	pub fn used_addresses() -> [H160; 5] {
+		[
+			precompile_address(1),
+			precompile_address(2),
+			precompile_address(3),
+			precompile_address(4),
+			KITTIES_ERC721_ADDRESS,
+		]
+	}
+}
+
+impl PrecompileSet for RuntimePrecompiles {
+	fn execute(
+		&self,
+		address: H160,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> Option<PrecompileResult> {
+		match address {
+			a if a == precompile_address(1) =>
+				Some(ECRecover::execute(input, target_gas, context, is_static)),
+			a if a == precompile_address(2) =>
+				Some(Sha256::execute(input, target_gas, context, is_static)),
+			a if a == precompile_address(3) =>
+				Some(Ripemd160::execute(input, target_gas, context, is_static)),
+			a if a == precompile_address(4) =>
+				Some(Identity::execute(input, target_gas, context, is_static)),
+			a if a == KITTIES_ERC721_ADDRESS =>
+				Some(KittiesErc721::execute(input, target_gas, context, is_static)),
+			_ => None,
+		}
+	}
+
+	fn is_precompile(&self, address: H160) -> bool {
+		Self::used_addresses().contains(&address)
+	}
+}
+
+/// `balanceOf(address)`
+pub const SELECTOR_BALANCE_OF: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+/// `ownerOf(uint256)`
+pub const SELECTOR_OWNER_OF: [u8; 4] = [0x63, 0x52, 0x21, 0x1e];
+/// `transferFrom(address,address,uint256)`
+pub const SELECTOR_TRANSFER_FROM: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+/// `Transfer(address,address,uint256)`
+pub const TRANSFER_EVENT: [u8; 32] = [
+	0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa,
+	0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3, 0xef,
+];
+
+/// Exposes `Kitties` as an ERC-721 token, whose token id is the 256 bit id of a kitty.
+///
+/// Addresses are mapped to accounts by `TruncatedAddressMapping`, so a kitty is owned by the
+/// first 20 bytes of the account of its owner. `balanceOf` counts the kitties of the account of
+/// an address, and `transferFrom` only transfers kitties of the caller: approvals are not
+/// supported. Transfers are dispatched as `Kitties::transfer` calls, so they are paused along with
+/// it. As they are not reverted with the EVM frame calling them, contracts may not transfer, not
+/// even from their constructor: only the sender of a transaction calling the precompile itself
+/// may.
+pub struct KittiesErc721;
+
+impl KittiesErc721 {
+	fn error(message: &'static str) -> PrecompileFailure {
+		PrecompileFailure::Error { exit_status: ExitError::Other(message.into()) }
+	}
+
+	/// Charge `reads` and `writes` of the database, failing if it exceeds `target_gas`.
+	fn cost(target_gas: Option<u64>, reads: u64, writes: u64) -> Result<u64, PrecompileFailure> {
+		let weight = RocksDbWeight::get().reads_writes(reads, writes);
+		let cost = <Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight);
+		match target_gas {
+			Some(gas) if gas < cost =>
+				Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas }),
+			_ => Ok(cost),
+		}
+	}
+
+	/// The 32 byte word of the ABI encoded argument `index`, after the selector.
+	fn argument(input: &[u8], index: usize) -> Result<&[u8], PrecompileFailure> {
+		let start = 4 + 32 * index;
+		input.get(start..start + 32).ok_or_else(|| Self::error("Input too short"))
+	}
+
+	fn address_argument(input: &[u8], index: usize) -> Result<H160, PrecompileFailure> {
+		Ok(H160::from_slice(&Self::argument(input, index)?[12..]))
+	}
+
+	fn kitty_argument(input: &[u8], index: usize) -> Result<H256, PrecompileFailure> {
+		Ok(H256::from_slice(Self::argument(input, index)?))
+	}
+
+	fn output(cost: u64, output: Vec<u8>, logs: Vec<Log>) -> PrecompileResult {
+		Ok(PrecompileOutput { exit_status: ExitSucceed::Returned, cost, output, logs })
+	}
+
+	fn balance_of(input: &[u8], target_gas: Option<u64>) -> PrecompileResult {
+		let cost = Self::cost(target_gas, 1, 0)?;
+		let owner = TruncatedAddressMapping::into_account_id(Self::address_argument(input, 0)?);
+		let mut balance = [0u8; 32];
+		U256::from(Kitties::kittes_owned(&owner).len()).to_big_endian(&mut balance);
+		Self::output(cost, balance.to_vec(), vec![])
+	}
+
+	fn owner_of(input: &[u8], target_gas: Option<u64>) -> PrecompileResult {
+		let cost = Self::cost(target_gas, 1, 0)?;
+		let kitty = Kitties::kitties(Self::kitty_argument(input, 0)?)
+			.ok_or_else(|| Self::error("Kitty does not exist"))?;
+		let owner = TruncatedAddressMapping::address_of(&kitty.owner);
+		Self::output(cost, H256::from(owner).as_bytes().to_vec(), vec![])
+	}
+
+	fn transfer_from(input: &[u8], target_gas: Option<u64>, context: &Context) -> PrecompileResult {
+		let cost = Self::cost(target_gas, 5, 3)?;
+		let from = Self::address_argument(input, 0)?;
+		let to = Self::address_argument(input, 1)?;
+		let kitty_id = Self::kitty_argument(input, 2)?;
+		if from != context.caller {
+			return Err(Self::error("Caller is not the sender"))
+		}
+		if transaction_origin() != Some(context.caller) {
+			return Err(Self::error("Caller is not the transaction origin"))
+		}
+
+		let kitty =
+			Kitties::kitties(kitty_id).ok_or_else(|| Self::error("Kitty does not exist"))?;
+		if TruncatedAddressMapping::address_of(&kitty.owner) != from {
+			return Err(Self::error("Sender is not the kitty owner"))
+		}
+		dispatch_kitty_transfer(
+			&kitty.owner,
+			TruncatedAddressMapping::into_account_id(to),
+			kitty_id,
+		)
+		.map_err(|_| Self::error("Kitty transfer failed"))?;
+
+		let log = Log {
+			address: context.address,
+			topics: vec![H256(TRANSFER_EVENT), H256::from(from), H256::from(to), kitty_id],
+			data: vec![],
+		};
+		Self::output(cost, vec![], vec![log])
+	}
+}
+
+impl Precompile for KittiesErc721 {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> PrecompileResult {
+		match input.get(0..4) {
+			Some(selector) if selector == SELECTOR_BALANCE_OF =>
+				Self::balance_of(input, target_gas),
+			Some(selector) if selector == SELECTOR_OWNER_OF => Self::owner_of(input, target_gas),
+			Some(selector) if selector == SELECTOR_TRANSFER_FROM => {
+				if is_static {
+					return Err(Self::error("Transfer in a static call"))
+				}
+				Self::transfer_from(input, target_gas, context)
+			},
+			_ => Err(Self::error("Unknown selector")),
+		}
+	}
+}
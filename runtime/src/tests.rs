@@ -0,0 +1,1043 @@
+use crate::*;
+use codec::{Decode, Encode};
+use fp_evm::PrecompileSet;
+use frame_support::{
+	assert_err, assert_ok,
+	traits::{Contains, Currency, OnFinalize, OnInitialize},
+	weights::{DispatchClass, GetDispatchInfo, WeightToFeePolynomial},
+};
+use pallet_democracy::{AccountVote, Conviction, Vote};
+use pallet_evm::{AddressMapping, FeeCalculator, Runner};
+use pallet_kitties::NativeOrAsset;
+use sp_core::{H160, H256, U256};
+use sp_keyring::{Ed25519Keyring, Sr25519Keyring};
+use sp_runtime::{traits::Hash, DispatchResult};
+
+fn account(seed: u8) -> AccountId {
+	AccountId::from([seed; 32])
+}
+
+const VALIDATORS: [(Sr25519Keyring, Ed25519Keyring); 3] = [
+	(Sr25519Keyring::Alice, Ed25519Keyring::Alice),
+	(Sr25519Keyring::Bob, Ed25519Keyring::Bob),
+	(Sr25519Keyring::Charlie, Ed25519Keyring::Charlie),
+];
+
+const STAKE: Balance = 1_000 * UNIT;
+
+// Build genesis storage with accounts 1, 2 and 3 funded and sitting on the council, account 9
+// holding the sudo key, and Alice, Bob and Charlie validating, each bonding `STAKE` with its own
+// account as stash and controller.
+fn new_test_ext() -> sp_io::TestExternalities {
+	let members = vec![account(1), account(2), account(3)];
+	let validators: Vec<AccountId> =
+		VALIDATORS.iter().map(|(account, _)| account.to_account_id()).collect();
+	let keys = VALIDATORS
+		.iter()
+		.map(|(account, grandpa)| {
+			let keys = opaque::SessionKeys {
+				aura: account.public().into(),
+				grandpa: grandpa.public().into(),
+			};
+			(account.to_account_id(), account.to_account_id(), keys)
+		})
+		.collect();
+	let storage = GenesisConfig {
+		balances: BalancesConfig {
+			balances: members
+				.iter()
+				.chain(validators.iter())
+				.chain(Some(&Sr25519Keyring::Dave.to_account_id()))
+				.cloned()
+				.map(|k| (k, 1_000_000 * UNIT))
+				.collect(),
+		},
+		council: CouncilConfig { members, phantom: Default::default() },
+		sudo: SudoConfig { key: Some(account(9)) },
+		staking: StakingConfig {
+			validator_count: 3,
+			minimum_validator_count: 1,
+			stakers: validators
+				.iter()
+				.map(|v| (v.clone(), v.clone(), STAKE, StakerStatus::Validator))
+				.collect(),
+			..Default::default()
+		},
+		session: SessionConfig { keys },
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn run_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		let block = System::block_number() + 1;
+		System::set_block_number(block);
+		Democracy::on_initialize(block);
+		Scheduler::on_initialize(block);
+	}
+}
+
+// Propose `call` to the council with `threshold`, let every other member approve and close it.
+fn council_motion(call: Call, threshold: u32) {
+	let length = call.encode().len() as u32;
+	let weight = call.get_dispatch_info().weight;
+	let hash = BlakeTwo256::hash_of(&call);
+	let index = Council::proposal_count();
+
+	assert_ok!(Council::propose(Origin::signed(account(1)), threshold, Box::new(call), length));
+	if threshold > 1 {
+		assert_ok!(Council::vote(Origin::signed(account(2)), hash, index, true));
+		assert_ok!(Council::vote(Origin::signed(account(3)), hash, index, true));
+		assert_ok!(Council::close(Origin::signed(account(1)), hash, index, weight, length));
+	}
+}
+
+#[test]
+fn council_majority_motion_should_execute() {
+	new_test_ext().execute_with(|| {
+		council_motion(Call::TemplateModule(pallet_template::Call::set_something { value: 42 }), 2);
+		assert_eq!(TemplateModule::something(), 42);
+	});
+}
+
+#[test]
+fn council_minority_motion_should_not_execute() {
+	new_test_ext().execute_with(|| {
+		// A single member is not a strict majority of three.
+		council_motion(Call::TemplateModule(pallet_template::Call::set_something { value: 42 }), 1);
+		assert_eq!(TemplateModule::something(), 0);
+	});
+}
+
+#[test]
+fn fast_tracked_referendum_should_pass_and_execute() {
+	new_test_ext().execute_with(|| {
+		let proposal = Call::TemplateModule(pallet_template::Call::set_something { value: 7 });
+		let proposal_hash = BlakeTwo256::hash_of(&proposal);
+		assert_ok!(Democracy::note_preimage(Origin::signed(account(1)), proposal.encode()));
+
+		council_motion(
+			Call::Democracy(pallet_democracy::Call::external_propose_majority { proposal_hash }),
+			3,
+		);
+		// Voting periods this short need the unanimous council as instant origin.
+		council_motion(
+			Call::Democracy(pallet_democracy::Call::fast_track {
+				proposal_hash,
+				voting_period: 2,
+				delay: 1,
+			}),
+			3,
+		);
+
+		let vote = AccountVote::Standard {
+			vote: Vote { aye: true, conviction: Conviction::Locked1x },
+			balance: UNIT,
+		};
+		assert_ok!(Democracy::vote(Origin::signed(account(2)), 0, vote));
+
+		// The referendum ends at block 3 and is enacted one block later.
+		run_to_block(3);
+		assert_eq!(TemplateModule::something(), 0);
+		run_to_block(4);
+		assert_eq!(TemplateModule::something(), 7);
+	});
+}
+
+#[test]
+fn council_should_pause_pallets_but_not_system() {
+	new_test_ext().execute_with(|| {
+		let create_kitty = Call::Kitties(pallet_kitties::Call::create_kitty {});
+		let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+		assert!(TxPause::contains(&create_kitty));
+
+		council_motion(
+			Call::TxPause(pallet_tx_pause::Call::pause_pallet { pallet: b"Kitties".to_vec() }),
+			2,
+		);
+		assert!(!TxPause::contains(&create_kitty));
+		assert!(TxPause::contains(&remark));
+
+		council_motion(
+			Call::TxPause(pallet_tx_pause::Call::pause_pallet { pallet: b"System".to_vec() }),
+			2,
+		);
+		assert!(TxPause::contains(&remark));
+	});
+}
+
+#[test]
+fn multisig_utility_and_proxy_should_not_be_pausable() {
+	new_test_ext().execute_with(|| {
+		for pallet in [&b"Multisig"[..], b"Utility", b"Proxy"] {
+			assert_err!(
+				TxPause::pause_pallet(Origin::root(), pallet.to_vec()),
+				pallet_tx_pause::Error::<Runtime>::Unpausable
+			);
+			assert_err!(
+				TxPause::pause_call(Origin::root(), pallet.to_vec(), b"as_multi".to_vec()),
+				pallet_tx_pause::Error::<Runtime>::Unpausable
+			);
+		}
+		assert!(TxPause::contains(&Call::Utility(pallet_utility::Call::batch { calls: vec![] })));
+	});
+}
+
+fn start_session(index: u32) {
+	while Session::current_index() < index {
+		let block = System::block_number() + 1;
+		System::set_block_number(block);
+		// Sessions last as long as on a live chain, so that eras earn rewards.
+		Timestamp::set_timestamp(block as u64 * SessionPeriod::get() as u64 * MILLISECS_PER_BLOCK);
+		Session::rotate_session();
+		Grandpa::on_finalize(block);
+		Staking::on_finalize(block);
+	}
+}
+
+#[test]
+fn validator_set_should_add_validators_next_to_the_elected_ones() {
+	new_test_ext().execute_with(|| {
+		let dave = Sr25519Keyring::Dave.to_account_id();
+		let keys = opaque::SessionKeys {
+			aura: Sr25519Keyring::Dave.public().into(),
+			grandpa: Ed25519Keyring::Dave.public().into(),
+		};
+		// Dave does not bond, so only validators of `ValidatorSet` may set their keys.
+		assert!(Session::set_keys(Origin::signed(dave.clone()), keys.clone(), vec![]).is_err());
+
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), dave.clone()));
+		assert_ok!(Session::set_keys(Origin::signed(dave.clone()), keys, vec![]));
+		start_session(2);
+		assert!(Session::validators().contains(&dave));
+		assert_eq!(Session::validators().len(), 4);
+		assert_eq!(Staking::eras_stakers(Staking::active_era().unwrap().index, &dave).total, 0);
+
+		// Eras keep being planned with the permissioned validator in the set.
+		start_active_era(1);
+		assert!(Session::validators().contains(&dave));
+		assert_eq!(Session::validators().len(), 4);
+
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), dave.clone()));
+		start_session(Session::current_index() + 2);
+		assert!(!Session::validators().contains(&dave));
+		assert_eq!(Session::validators().len(), 3);
+	});
+}
+
+fn start_active_era(era: u32) {
+	start_session(era * SessionsPerEra::get());
+	assert_eq!(Staking::active_era().unwrap().index, era);
+}
+
+#[test]
+fn staking_election_should_elect_the_highest_bonded_candidates() {
+	new_test_ext().execute_with(|| {
+		let dave = Sr25519Keyring::Dave.to_account_id();
+		assert_ok!(Staking::bond(
+			Origin::signed(dave.clone()),
+			dave.clone().into(),
+			2 * STAKE,
+			pallet_staking::RewardDestination::Staked,
+		));
+		assert_ok!(Staking::validate(Origin::signed(dave.clone()), Default::default()));
+		let keys = opaque::SessionKeys {
+			aura: Sr25519Keyring::Dave.public().into(),
+			grandpa: Ed25519Keyring::Dave.public().into(),
+		};
+		assert_ok!(Session::set_keys(Origin::signed(dave.clone()), keys, vec![]));
+
+		start_active_era(1);
+		// Dave outbids one of the three genesis validators for the three seats.
+		assert_eq!(Staking::eras_stakers(1, &dave).total, 2 * STAKE);
+		assert_eq!(pallet_staking::ErasStakers::<Runtime>::iter_prefix(1).count(), 3);
+		assert!(Session::validators().contains(&dave));
+	});
+}
+
+#[test]
+fn era_rewards_should_be_paid_out_to_validators() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		start_active_era(1);
+		Staking::reward_by_ids(vec![(alice.clone(), 20)]);
+		start_active_era(2);
+		assert!(Staking::eras_validator_reward(1).unwrap() > 0);
+
+		let balance = Balances::free_balance(&alice);
+		let bonded = Staking::ledger(&alice).unwrap().active;
+		assert_ok!(Staking::payout_stakers(Origin::signed(account(1)), alice.clone(), 1));
+		// The reward is added to the stake of Alice.
+		assert!(Balances::free_balance(&alice) > balance);
+		assert_eq!(
+			Staking::ledger(&alice).unwrap().active - bonded,
+			Balances::free_balance(&alice) - balance
+		);
+	});
+}
+
+// Sign two prevotes of `keyring` for different blocks in the same round.
+fn equivocation_proof(
+	set_id: fg_primitives::SetId,
+	keyring: Ed25519Keyring,
+	first: (u64, H256, BlockNumber),
+	second: (u64, H256, BlockNumber),
+) -> fg_primitives::EquivocationProof<H256, BlockNumber> {
+	let signed_prevote = |(round, target_hash, target_number): (u64, H256, BlockNumber)| {
+		let prevote = finality_grandpa::Prevote { target_hash, target_number };
+		let message = finality_grandpa::Message::Prevote(prevote.clone());
+		let payload = fg_primitives::localized_payload(round, set_id, &message);
+		(prevote, keyring.sign(&payload).into())
+	};
+
+	fg_primitives::EquivocationProof::new(
+		set_id,
+		fg_primitives::Equivocation::Prevote(finality_grandpa::Equivocation {
+			round_number: first.0,
+			identity: keyring.public().into(),
+			first: signed_prevote(first),
+			second: signed_prevote(second),
+		}),
+	)
+}
+
+fn key_owner_proof(keyring: Ed25519Keyring) -> sp_session::MembershipProof {
+	let authority: GrandpaId = keyring.public().into();
+	Historical::prove((fg_primitives::KEY_TYPE, authority)).unwrap()
+}
+
+#[test]
+fn grandpa_equivocation_should_be_reported_as_offence() {
+	new_test_ext().execute_with(|| {
+		start_session(2);
+		let set_id = Grandpa::current_set_id();
+		let bob = Sr25519Keyring::Bob.to_account_id();
+		let proof = equivocation_proof(
+			set_id,
+			Ed25519Keyring::Bob,
+			(1, H256::random(), 1),
+			(1, H256::random(), 1),
+		);
+
+		assert_ok!(Grandpa::report_equivocation_unsigned(
+			Origin::none(),
+			Box::new(proof.clone()),
+			key_owner_proof(Ed25519Keyring::Bob),
+		));
+		assert!(System::events().iter().any(|record| matches!(record.event, Event::Offences(_))));
+		// Bob is slashed once the slash defer duration has passed.
+		assert!(pallet_staking::UnappliedSlashes::<Runtime>::iter()
+			.any(|(_, slashes)| slashes.iter().any(|slash| slash.validator == bob)));
+
+		// The same offence is only reported once.
+		assert_err!(
+			Grandpa::report_equivocation_unsigned(
+				Origin::none(),
+				Box::new(proof),
+				key_owner_proof(Ed25519Keyring::Bob),
+			),
+			pallet_grandpa::Error::<Runtime>::DuplicateOffenceReport
+		);
+	});
+}
+
+#[test]
+fn grandpa_equivocation_should_be_rejected_when_invalid() {
+	new_test_ext().execute_with(|| {
+		start_session(2);
+		let set_id = Grandpa::current_set_id();
+		let vote = (1, H256::random(), 1);
+
+		// Voting twice for the same block is no equivocation.
+		assert_err!(
+			Grandpa::report_equivocation_unsigned(
+				Origin::none(),
+				Box::new(equivocation_proof(set_id, Ed25519Keyring::Bob, vote, vote)),
+				key_owner_proof(Ed25519Keyring::Bob),
+			),
+			pallet_grandpa::Error::<Runtime>::InvalidEquivocationProof
+		);
+		// The key ownership proof has to be for the offender.
+		assert_err!(
+			Grandpa::report_equivocation_unsigned(
+				Origin::none(),
+				Box::new(equivocation_proof(
+					set_id,
+					Ed25519Keyring::Bob,
+					vote,
+					(1, H256::random(), 1)
+				)),
+				key_owner_proof(Ed25519Keyring::Charlie),
+			),
+			pallet_grandpa::Error::<Runtime>::InvalidKeyOwnershipProof
+		);
+		assert!(!System::events().iter().any(|record| matches!(record.event, Event::Offences(_))));
+	});
+}
+
+#[test]
+fn base_extrinsic_should_cost_a_tenth_of_a_milliunit() {
+	assert_eq!(WeightToFee::calc(&ExtrinsicBaseWeight::get()), MILLIUNIT / 10);
+	assert_eq!(WeightToFee::calc(&(10 * ExtrinsicBaseWeight::get())), MILLIUNIT);
+}
+
+#[test]
+fn fees_should_grow_with_full_blocks() {
+	new_test_ext().execute_with(|| {
+		let call = Call::Kitties(pallet_kitties::Call::create_kitty {});
+		let info = call.get_dispatch_info();
+		let len = call.encode().len() as u32;
+		// The multiplier only scales the weight part of the fee, so it has to outweigh rounding.
+		assert!(info.weight > 10 * ExtrinsicBaseWeight::get());
+		let weight_fee = || {
+			TransactionPayment::compute_fee_details(len, &info, 0)
+				.inclusion_fee
+				.unwrap()
+				.adjusted_weight_fee
+		};
+		let mut fee = weight_fee();
+		let mut multiplier = TransactionPayment::next_fee_multiplier();
+
+		// Fill the normal part of the block; the weight is never reset as blocks are not
+		// initialized.
+		let max_normal = BlockWeights::get().get(DispatchClass::Normal).max_total.unwrap();
+		System::register_extra_weight_unchecked(max_normal, DispatchClass::Normal);
+		for block in 2..12 {
+			System::set_block_number(block);
+			TransactionPayment::on_finalize(block);
+
+			assert!(TransactionPayment::next_fee_multiplier() > multiplier);
+			assert!(weight_fee() > fee);
+			multiplier = TransactionPayment::next_fee_multiplier();
+			fee = weight_fee();
+		}
+	});
+}
+
+#[test]
+fn fees_should_shrink_with_empty_blocks_down_to_the_minimum() {
+	new_test_ext().execute_with(|| {
+		pallet_transaction_payment::NextFeeMultiplier::<Runtime>::put(
+			Multiplier::saturating_from_integer(2),
+		);
+		TransactionPayment::on_finalize(1);
+		assert!(TransactionPayment::next_fee_multiplier() < Multiplier::saturating_from_integer(2));
+
+		pallet_transaction_payment::NextFeeMultiplier::<Runtime>::put(MinimumMultiplier::get());
+		TransactionPayment::on_finalize(2);
+		assert_eq!(TransactionPayment::next_fee_multiplier(), MinimumMultiplier::get());
+	});
+}
+
+#[test]
+fn batch_all_should_dispatch_every_call_or_none() {
+	new_test_ext().execute_with(|| {
+		let create_kitty = Call::Kitties(pallet_kitties::Call::create_kitty {});
+		assert_ok!(Utility::batch_all(
+			Origin::signed(account(1)),
+			vec![create_kitty.clone(), create_kitty.clone()],
+		));
+		assert_eq!(Kitties::kittes_owned(account(1)).len(), 2);
+
+		// The second call fails, so the first is reverted.
+		let transfer = Call::Balances(pallet_balances::Call::transfer {
+			dest: account(2).into(),
+			value: 2_000_000 * UNIT,
+		});
+		assert!(
+			Utility::batch_all(Origin::signed(account(1)), vec![create_kitty, transfer]).is_err()
+		);
+		assert_eq!(Kitties::kittes_owned(account(1)).len(), 2);
+	});
+}
+
+#[test]
+fn kitties_proxy_should_only_operate_kitties() {
+	new_test_ext().execute_with(|| {
+		let (player, server) = (account(1), account(4));
+		assert_ok!(Proxy::add_proxy(
+			Origin::signed(player.clone()),
+			server.clone(),
+			ProxyType::KittiesOnly,
+			0,
+		));
+
+		let create_kitty = Call::Kitties(pallet_kitties::Call::create_kitty {});
+		assert_ok!(Proxy::proxy(
+			Origin::signed(server.clone()),
+			player.clone(),
+			None,
+			Box::new(create_kitty.clone()),
+		));
+		assert_eq!(Kitties::kittes_owned(&player).len(), 1);
+		assert!(Kitties::kittes_owned(&server).is_empty());
+
+		// Neither directly nor through a batch can the server move funds of the player.
+		let balance = Balances::free_balance(&player);
+		let transfer = Call::Balances(pallet_balances::Call::transfer {
+			dest: server.clone().into(),
+			value: UNIT,
+		});
+		assert_ok!(Proxy::proxy(
+			Origin::signed(server.clone()),
+			player.clone(),
+			None,
+			Box::new(transfer.clone()),
+		));
+		assert_ok!(Proxy::proxy(
+			Origin::signed(server.clone()),
+			player.clone(),
+			None,
+			Box::new(Call::Utility(pallet_utility::Call::batch_all {
+				calls: vec![create_kitty, transfer],
+			})),
+		));
+		assert_eq!(Balances::free_balance(&player), balance);
+		assert_eq!(Kitties::kittes_owned(&player).len(), 1);
+	});
+}
+
+#[test]
+fn proxy_types_should_filter_calls() {
+	let transfer_kitty = Call::Kitties(pallet_kitties::Call::transfer {
+		to: account(2),
+		kitty_id: Default::default(),
+	});
+	let create_kitty = Call::Kitties(pallet_kitties::Call::create_kitty {});
+	let buy_kitty = Call::Kitties(pallet_kitties::Call::buy_kitty {
+		kitty_id: Default::default(),
+		asset: NativeOrAsset::Native,
+		bid_price: UNIT,
+	});
+	let set_price = Call::Kitties(pallet_kitties::Call::set_price {
+		kitty_id: Default::default(),
+		new_price: Some((NativeOrAsset::Native, 0)),
+	});
+	let create_claim =
+		Call::Poe(pallet_poe::Call::create_claim { claim: vec![0], expires_at: None });
+	let transfer =
+		Call::Balances(pallet_balances::Call::transfer { dest: account(2).into(), value: UNIT });
+
+	assert!(ProxyType::Any.filter(&transfer));
+	assert!(!ProxyType::NonTransfer.filter(&transfer));
+	assert!(!ProxyType::NonTransfer.filter(&transfer_kitty));
+	assert!(!ProxyType::NonTransfer.filter(&set_price));
+	assert!(ProxyType::NonTransfer.filter(&create_kitty));
+	assert!(ProxyType::KittiesOnly.filter(&transfer_kitty));
+	assert!(!ProxyType::KittiesOnly.filter(&buy_kitty));
+	assert!(!ProxyType::KittiesOnly.filter(&set_price));
+	assert!(!ProxyType::KittiesOnly.filter(&create_claim));
+	assert!(ProxyType::PoeOnly.filter(&create_claim));
+	assert!(!ProxyType::PoeOnly.filter(&create_kitty));
+
+	assert!(ProxyType::Any.is_superset(&ProxyType::KittiesOnly));
+	assert!(ProxyType::NonTransfer.is_superset(&ProxyType::PoeOnly));
+	assert!(!ProxyType::NonTransfer.is_superset(&ProxyType::KittiesOnly));
+	assert!(!ProxyType::KittiesOnly.is_superset(&ProxyType::Any));
+}
+
+#[test]
+fn multisig_should_dispatch_sudo_calls_once_approved_by_two_of_three() {
+	new_test_ext().execute_with(|| {
+		let multisig = Multisig::multi_account_id(&[account(1), account(2), account(3)], 2);
+		assert_ok!(Sudo::set_key(Origin::signed(account(9)), multisig.into()));
+
+		let call = Call::Sudo(pallet_sudo::Call::sudo {
+			call: Box::new(Call::TemplateModule(pallet_template::Call::set_something { value: 9 })),
+		});
+		let weight = call.get_dispatch_info().weight;
+		let data = call.encode();
+
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(account(1)),
+			2,
+			vec![account(2), account(3)],
+			None,
+			data.clone(),
+			false,
+			0,
+		));
+		assert_eq!(TemplateModule::something(), 0);
+
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(account(3)),
+			2,
+			vec![account(1), account(2)],
+			Some(Multisig::timepoint()),
+			data,
+			false,
+			weight,
+		));
+		assert_eq!(TemplateModule::something(), 9);
+	});
+}
+
+#[test]
+fn sudo_should_be_disabled_without_a_key() {
+	new_test_ext().execute_with(|| {
+		frame_support::storage::unhashed::kill(&frame_support::storage::storage_prefix(
+			b"Sudo", b"Key",
+		));
+		assert_eq!(Sudo::key(), None);
+
+		let call = Call::Sudo(pallet_sudo::Call::sudo {
+			call: Box::new(Call::TemplateModule(pallet_template::Call::set_something { value: 9 })),
+		});
+		for who in [account(9), AccountId::from([0u8; 32])] {
+			assert!(call.clone().dispatch(Origin::signed(who.clone())).is_err());
+			assert!(Sudo::set_key(Origin::signed(who.clone()), who.into()).is_err());
+		}
+		assert_eq!(TemplateModule::something(), 0);
+		assert_eq!(Sudo::key(), None);
+	});
+}
+
+fn schedule_named(id: &[u8], when: BlockNumber, call: Call) -> Call {
+	Call::Scheduler(pallet_scheduler::Call::schedule_named {
+		id: id.to_vec(),
+		when,
+		maybe_periodic: None,
+		priority: 0,
+		call: Box::new(call),
+	})
+}
+
+#[test]
+fn sudo_should_schedule_calls_for_a_future_block() {
+	new_test_ext().execute_with(|| {
+		let set_something = Call::TemplateModule(pallet_template::Call::set_something { value: 5 });
+		assert_ok!(Sudo::sudo(
+			Origin::signed(account(9)),
+			Box::new(schedule_named(b"set-something", 5, set_something)),
+		));
+
+		run_to_block(4);
+		assert_eq!(TemplateModule::something(), 0);
+		run_to_block(5);
+		assert_eq!(TemplateModule::something(), 5);
+	});
+}
+
+#[test]
+fn scheduled_calls_should_request_noted_preimages() {
+	new_test_ext().execute_with(|| {
+		let upgrade = Call::System(frame_system::Call::set_code { code: vec![1; 64] }).encode();
+		let hash = <Runtime as frame_system::Config>::Hashing::hash(&upgrade);
+		assert_ok!(Preimage::note_preimage(Origin::signed(account(1)), upgrade.clone()));
+		let deposit =
+			PreimageBaseDeposit::get() + PreimageByteDeposit::get() * upgrade.len() as Balance;
+		assert_eq!(Balances::reserved_balance(account(1)), deposit);
+		assert_err!(
+			Preimage::request_preimage(Origin::signed(account(1)), hash),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		let request = Call::Preimage(pallet_preimage::Call::request_preimage { hash });
+		assert_ok!(Sudo::sudo(
+			Origin::signed(account(9)),
+			Box::new(schedule_named(b"request-upgrade", 5, request)),
+		));
+		run_to_block(4);
+		assert_eq!(Balances::reserved_balance(account(1)), deposit);
+		run_to_block(5);
+		// Once requested, the preimage is kept without a deposit, and cannot be unnoted.
+		assert_eq!(Balances::reserved_balance(account(1)), 0);
+		assert_eq!(Preimage::get_preimage(&hash), Some(upgrade));
+		assert_err!(
+			Preimage::unnote_preimage(Origin::signed(account(1)), hash),
+			pallet_preimage::Error::<Runtime>::Requested
+		);
+	});
+}
+
+#[test]
+fn only_root_should_schedule_and_cancel_calls() {
+	new_test_ext().execute_with(|| {
+		let set_something = Call::TemplateModule(pallet_template::Call::set_something { value: 5 });
+		assert_err!(
+			Scheduler::schedule(
+				Origin::signed(account(1)),
+				5,
+				None,
+				0,
+				Box::new(set_something.clone()),
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(Sudo::sudo(
+			Origin::signed(account(9)),
+			Box::new(schedule_named(b"set-something", 5, set_something)),
+		));
+		assert_err!(
+			Scheduler::cancel_named(Origin::signed(account(1)), b"set-something".to_vec()),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		let cancel =
+			Call::Scheduler(pallet_scheduler::Call::cancel_named { id: b"set-something".to_vec() });
+		assert_ok!(Sudo::sudo(Origin::signed(account(9)), Box::new(cancel)));
+
+		run_to_block(5);
+		assert_eq!(TemplateModule::something(), 0);
+	});
+}
+
+#[test]
+fn proofs_should_carry_the_judged_identity_of_their_owner() {
+	new_test_ext().execute_with(|| {
+		let (owner, registrar) = (account(1), account(2));
+		council_motion(
+			Call::Identity(pallet_identity::Call::add_registrar { account: registrar.clone() }),
+			2,
+		);
+		assert_ok!(Identity::set_fee(Origin::signed(registrar.clone()), 0, UNIT));
+
+		let info = pallet_identity::IdentityInfo {
+			additional: Default::default(),
+			display: pallet_identity::Data::Raw(b"Alice".to_vec().try_into().unwrap()),
+			legal: pallet_identity::Data::None,
+			web: pallet_identity::Data::None,
+			riot: pallet_identity::Data::None,
+			email: pallet_identity::Data::None,
+			pgp_fingerprint: None,
+			image: pallet_identity::Data::None,
+			twitter: pallet_identity::Data::None,
+		};
+		assert_ok!(Identity::set_identity(Origin::signed(owner.clone()), Box::new(info)));
+		assert_ok!(Poe::create_claim(Origin::signed(owner.clone()), b"proof".to_vec(), None));
+		// Unjudged identities are not attributed.
+		assert_eq!(Poe::claim_details(&b"proof".to_vec()).unwrap().owner_display_name, None);
+
+		let registrar_balance = Balances::free_balance(&registrar);
+		assert_ok!(Identity::request_judgement(Origin::signed(owner.clone()), 0, UNIT));
+		assert_ok!(Identity::provide_judgement(
+			Origin::signed(registrar.clone()),
+			0,
+			owner.clone().into(),
+			pallet_identity::Judgement::Reasonable,
+		));
+		// The registrar is paid its fee in the native currency.
+		assert_eq!(Balances::free_balance(&registrar), registrar_balance + UNIT);
+		assert_eq!(
+			Poe::claim_details(&b"proof".to_vec()).unwrap().owner_display_name,
+			Some(b"Alice".to_vec())
+		);
+	});
+}
+
+#[test]
+fn only_validators_should_anchor_batches() {
+	new_test_ext().execute_with(|| {
+		let root = H256::repeat_byte(1);
+		assert_err!(
+			Poe::anchor_batch(Origin::signed(account(1)), root, 3),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		let validator = Sr25519Keyring::Alice.to_account_id();
+		assert_ok!(Poe::anchor_batch(Origin::signed(validator.clone()), root, 3));
+		assert_eq!(Poe::anchors(root).unwrap().submitter, validator);
+	});
+}
+
+// Deploy the contract calling the kitties chain extension, endowed by account 1.
+fn instantiate_kitties_extension() -> AccountId {
+	let code =
+		wat::parse_file(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/kitties_extension.wat"))
+			.unwrap();
+	Contracts::bare_instantiate(
+		account(1),
+		100 * UNIT,
+		BlockWeights::get().max_block,
+		pallet_contracts_primitives::Code::Upload(code.into()),
+		vec![],
+		vec![],
+		false,
+	)
+	.result
+	.unwrap()
+	.account_id
+}
+
+fn call_kitties_extension(contract: &AccountId, func_id: u32, input: impl Encode) -> Vec<u8> {
+	Contracts::bare_call(
+		account(1),
+		contract.clone(),
+		0,
+		BlockWeights::get().max_block,
+		(func_id, input).encode(),
+		false,
+	)
+	.result
+	.unwrap()
+	.data
+	.0
+}
+
+#[test]
+fn contracts_should_read_kitties_through_the_chain_extension() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Kitties::create_kitty(Origin::signed(account(2))));
+		let kitty_id = Kitties::kittes_owned(account(2))[0];
+		let contract = instantiate_kitties_extension();
+
+		let owner = call_kitties_extension(&contract, 1, kitty_id);
+		assert_eq!(Option::<AccountId>::decode(&mut &owner[..]).unwrap(), Some(account(2)));
+		let owner = call_kitties_extension(&contract, 1, H256::zero());
+		assert_eq!(Option::<AccountId>::decode(&mut &owner[..]).unwrap(), None);
+
+		let owned = call_kitties_extension(&contract, 2, account(2));
+		assert_eq!(Vec::<H256>::decode(&mut &owned[..]).unwrap(), vec![kitty_id]);
+	});
+}
+
+#[test]
+fn contracts_should_only_transfer_their_own_kitties_through_the_chain_extension() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Kitties::create_kitty(Origin::signed(account(2))));
+		let kitty_id = Kitties::kittes_owned(account(2))[0];
+		let contract = instantiate_kitties_extension();
+
+		let result = call_kitties_extension(&contract, 3, (account(3), kitty_id));
+		assert_eq!(
+			DispatchResult::decode(&mut &result[..]).unwrap(),
+			Err(pallet_kitties::Error::<Runtime>::NotKittyOwner.into())
+		);
+		assert_eq!(Kitties::kitties(kitty_id).unwrap().owner, account(2));
+
+		assert_ok!(Kitties::transfer(Origin::signed(account(2)), contract.clone(), kitty_id));
+		let result = call_kitties_extension(&contract, 3, (account(3), kitty_id));
+		assert_eq!(DispatchResult::decode(&mut &result[..]).unwrap(), Ok(()));
+		assert_eq!(Kitties::kitties(kitty_id).unwrap().owner, account(3));
+		assert!(Kitties::kittes_owned(&contract).is_empty());
+	});
+}
+
+fn erc721_input(selector: [u8; 4], args: &[H256]) -> Vec<u8> {
+	selector
+		.iter()
+		.chain(args.iter().flat_map(|arg| arg.as_bytes()))
+		.cloned()
+		.collect()
+}
+
+// Run an EVM transaction of `source` calling `target` with `input`.
+fn evm_call(source: H160, target: H160, input: Vec<u8>) {
+	assert!(<Runtime as pallet_evm::Config>::Runner::call(
+		source,
+		target,
+		input,
+		U256::zero(),
+		1_000_000,
+		Some(FixedGasPrice::min_gas_price()),
+		None,
+		None,
+		vec![],
+		<Runtime as pallet_evm::Config>::config(),
+	)
+	.is_ok());
+}
+
+fn call_kitties_erc721(caller: H160, selector: [u8; 4], args: &[H256]) -> fp_evm::PrecompileResult {
+	let context = fp_evm::Context {
+		address: precompiles::KITTIES_ERC721_ADDRESS,
+		caller,
+		apparent_value: U256::zero(),
+	};
+	let input = erc721_input(selector, args);
+	// As if `caller` sent a transaction calling the precompile.
+	precompiles::with_transaction_origin(caller, || {
+		PrecompilesValue::get()
+			.execute(precompiles::KITTIES_ERC721_ADDRESS, &input, None, &context, false)
+			.unwrap()
+	})
+}
+
+#[test]
+fn contracts_should_not_transfer_kitties_while_kitties_are_paused() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Kitties::create_kitty(Origin::signed(account(2))));
+		let kitty_id = Kitties::kittes_owned(account(2))[0];
+		let contract = instantiate_kitties_extension();
+		assert_ok!(Kitties::transfer(Origin::signed(account(2)), contract.clone(), kitty_id));
+
+		assert_ok!(TxPause::pause_pallet(Origin::root(), b"Kitties".to_vec()));
+		let result = call_kitties_extension(&contract, 3, (account(3), kitty_id));
+		assert!(DispatchResult::decode(&mut &result[..]).unwrap().is_err());
+		assert_eq!(Kitties::kitties(kitty_id).unwrap().owner, contract);
+	});
+}
+
+#[test]
+fn kitties_erc721_should_report_owners_and_balances() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Kitties::create_kitty(Origin::signed(account(2))));
+		let kitty_id = Kitties::kittes_owned(account(2))[0];
+		let owner = H160::repeat_byte(2);
+
+		let output = call_kitties_erc721(owner, precompiles::SELECTOR_OWNER_OF, &[kitty_id])
+			.unwrap()
+			.output;
+		assert_eq!(output, H256::from(owner).as_bytes());
+		assert!(
+			call_kitties_erc721(owner, precompiles::SELECTOR_OWNER_OF, &[H256::zero()]).is_err()
+		);
+
+		// Balances count the kitties of the account of an EVM address.
+		let evm_address = H160::repeat_byte(9);
+		assert_ok!(Kitties::transfer(
+			Origin::signed(account(2)),
+			TruncatedAddressMapping::into_account_id(evm_address),
+			kitty_id,
+		));
+		let output = call_kitties_erc721(
+			owner,
+			precompiles::SELECTOR_BALANCE_OF,
+			&[H256::from(evm_address)],
+		)
+		.unwrap()
+		.output;
+		assert_eq!(U256::from_big_endian(&output), U256::one());
+	});
+}
+
+#[test]
+fn kitties_erc721_should_only_transfer_kitties_of_the_caller() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Kitties::create_kitty(Origin::signed(account(2))));
+		let kitty_id = Kitties::kittes_owned(account(2))[0];
+		let (owner, dest) = (H160::repeat_byte(2), H160::repeat_byte(9));
+		let args = [H256::from(owner), H256::from(dest), kitty_id];
+
+		// Neither on behalf of the owner, nor from an account which does not own it.
+		assert!(call_kitties_erc721(dest, precompiles::SELECTOR_TRANSFER_FROM, &args).is_err());
+		let args_from_dest = [H256::from(dest), H256::from(owner), kitty_id];
+		assert!(call_kitties_erc721(dest, precompiles::SELECTOR_TRANSFER_FROM, &args_from_dest)
+			.is_err());
+		assert_eq!(Kitties::kitties(kitty_id).unwrap().owner, account(2));
+
+		let output =
+			call_kitties_erc721(owner, precompiles::SELECTOR_TRANSFER_FROM, &args).unwrap();
+		assert_eq!(output.logs.len(), 1);
+		assert_eq!(output.logs[0].topics[0], H256(precompiles::TRANSFER_EVENT));
+		assert_eq!(
+			Kitties::kitties(kitty_id).unwrap().owner,
+			TruncatedAddressMapping::into_account_id(dest)
+		);
+		let output = call_kitties_erc721(owner, precompiles::SELECTOR_OWNER_OF, &[kitty_id])
+			.unwrap()
+			.output;
+		assert_eq!(output, H256::from(dest).as_bytes());
+	});
+}
+
+#[test]
+fn kitties_erc721_should_not_transfer_while_kitties_are_paused() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Kitties::create_kitty(Origin::signed(account(2))));
+		let kitty_id = Kitties::kittes_owned(account(2))[0];
+		let (owner, dest) = (H160::repeat_byte(2), H160::repeat_byte(9));
+		let args = [H256::from(owner), H256::from(dest), kitty_id];
+
+		assert_ok!(TxPause::pause_pallet(Origin::root(), b"Kitties".to_vec()));
+		assert!(call_kitties_erc721(owner, precompiles::SELECTOR_TRANSFER_FROM, &args).is_err());
+		assert_eq!(Kitties::kitties(kitty_id).unwrap().owner, account(2));
+
+		assert_ok!(TxPause::unpause_pallet(Origin::root(), b"Kitties".to_vec()));
+		assert_ok!(call_kitties_erc721(owner, precompiles::SELECTOR_TRANSFER_FROM, &args));
+	});
+}
+
+#[test]
+fn kitties_erc721_transfers_should_not_survive_a_reverting_contract() {
+	new_test_ext().execute_with(|| {
+		// Forwards its input to the kitties precompile, then reverts.
+		let contract = H160::repeat_byte(0xc0);
+		pallet_evm::AccountCodes::<Runtime>::insert(
+			contract,
+			vec![
+				0x36, 0x60, 0x00, 0x60, 0x00, 0x37, 0x60, 0x00, 0x60, 0x00, 0x36, 0x60, 0x00, 0x60,
+				0x00, 0x61, 0x04, 0x00, 0x5a, 0xf1, 0x60, 0x00, 0x60, 0x00, 0xfd,
+			],
+		);
+		let contract_account = TruncatedAddressMapping::into_account_id(contract);
+		let sender = H160::repeat_byte(9);
+		let sender_account = TruncatedAddressMapping::into_account_id(sender);
+		let _ = Balances::deposit_creating(&sender_account, UNIT);
+		assert_ok!(Kitties::create_kitty(Origin::signed(account(2))));
+		let kitty_id = Kitties::kittes_owned(account(2))[0];
+		assert_ok!(Kitties::transfer(
+			Origin::signed(account(2)),
+			contract_account.clone(),
+			kitty_id
+		));
+
+		let args = [H256::from(contract), H256::from(sender), kitty_id];
+		evm_call(sender, contract, erc721_input(precompiles::SELECTOR_TRANSFER_FROM, &args));
+		assert_eq!(Kitties::kitties(kitty_id).unwrap().owner, contract_account);
+
+		// Whereas a transaction calling the precompile itself transfers.
+		assert_ok!(Kitties::transfer(Origin::signed(contract_account), sender_account, kitty_id));
+		let dest = H160::repeat_byte(7);
+		let args = [H256::from(sender), H256::from(dest), kitty_id];
+		evm_call(
+			sender,
+			precompiles::KITTIES_ERC721_ADDRESS,
+			erc721_input(precompiles::SELECTOR_TRANSFER_FROM, &args),
+		);
+		assert_eq!(
+			Kitties::kitties(kitty_id).unwrap().owner,
+			TruncatedAddressMapping::into_account_id(dest)
+		);
+	});
+}
+
+#[test]
+fn kitties_erc721_should_not_transfer_from_a_reverting_constructor() {
+	new_test_ext().execute_with(|| {
+		let sender = H160::repeat_byte(9);
+		let _ = Balances::deposit_creating(&TruncatedAddressMapping::into_account_id(sender), UNIT);
+		// The address of the first contract `sender` creates: keccak256(rlp([sender, 0]))[12..].
+		let mut rlp = vec![0xd6, 0x94];
+		rlp.extend_from_slice(sender.as_bytes());
+		rlp.push(0x80);
+		let contract = H160::from_slice(&sp_core::keccak_256(&rlp)[12..]);
+		let contract_account = TruncatedAddressMapping::into_account_id(contract);
+		assert_ok!(Kitties::create_kitty(Origin::signed(account(2))));
+		let kitty_id = Kitties::kittes_owned(account(2))[0];
+		assert_ok!(Kitties::transfer(
+			Origin::signed(account(2)),
+			contract_account.clone(),
+			kitty_id
+		));
+
+		// Calls the kitties precompile with the input appended to it, then reverts. While it
+		// runs, the contract has no code yet.
+		let args = [H256::from(contract), H256::from(sender), kitty_id];
+		let mut init = vec![
+			0x60, 0x64, 0x60, 0x1b, 0x60, 0x00, 0x39, 0x60, 0x00, 0x60, 0x00, 0x60, 0x64, 0x60,
+			0x00, 0x60, 0x00, 0x61, 0x04, 0x00, 0x5a, 0xf1, 0x60, 0x00, 0x60, 0x00, 0xfd,
+		];
+		init.extend(erc721_input(precompiles::SELECTOR_TRANSFER_FROM, &args));
+		let info = <Runtime as pallet_evm::Config>::Runner::create(
+			sender,
+			init,
+			U256::zero(),
+			1_000_000,
+			Some(FixedGasPrice::min_gas_price()),
+			None,
+			None,
+			vec![],
+			<Runtime as pallet_evm::Config>::config(),
+		)
+		.unwrap();
+		assert!(matches!(info.exit_reason, evm::ExitReason::Revert(_)));
+		assert_eq!(Kitties::kitties(kitty_id).unwrap().owner, contract_account);
+		assert!(Kitties::kittes_owned(TruncatedAddressMapping::into_account_id(sender)).is_empty());
+	});
+}